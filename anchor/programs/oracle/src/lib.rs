@@ -1,38 +1,47 @@
 #![allow(clippy::result_large_err)]
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{clock::Clock, program::invoke, system_instruction}; 
+use anchor_lang::solana_program::{
+    clock::Clock,
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
 use anchor_spl::associated_token::AssociatedToken; 
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        default_account_state::DefaultAccountState, transfer_hook::TransferHook,
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::AccountState,
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 
 declare_id!("9oPLPE3PC9ok7T8UL9ZMfrNyPkhtaHh1mM9wFk2fWEVJ");
 
 const MAX_HISTORY_ENTRIES: usize = 128;
 const MAX_TARGET_RECORDS: usize = 64;
 const MAX_USER_VOTES: usize = 64;
+const MAX_DEPOSIT_LOTS: usize = 16;
+const MAX_PENDING_WITHDRAWALS: usize = 8;
+const MAX_STAKE_CHECKPOINTS: usize = 16;
+const MAX_RECENT_SUBMISSIONS: usize = 8;
 const DENOMINATOR: u64 = 100_000;
+/// Largest vote-escrow boost. The boost is added on top of the unlocked
+/// stake, so a full-length lock weighs at most five times as much.
+const MAX_VE_BOOST_BPS: u64 = 4 * DENOMINATOR;
 const WAD: u128 = 1_000_000_000_000_000_000;
+/// Stands in for `weight_mint` on oracles weighted by staked lamports, which
+/// derive from `[b"native_oracle", authority]` instead.
+const NATIVE_WEIGHT_MINT: Pubkey = Pubkey::new_from_array([0; 32]);
 
 #[program]
 pub mod oracle {
     use super::*;
 
     pub fn initialize(ctx: Context<InitializeOracle>, params: InitializeOracleParams) -> Result<()> {
-        require!(!params.name.is_empty(), OracleError::InvalidName);
-        require!(
-            params.name.len() <= OracleState::MAX_NAME_LEN,
-            OracleError::InvalidName
-        );
-        require!(
-            params.description.len() <= OracleState::MAX_DESCRIPTION_LEN,
-            OracleError::InvalidDescription
-        );
-        require!(params.reward_bps <= DENOMINATOR, OracleError::InvalidRewardRate);
+        validate_weight_mint(&ctx.accounts.weight_mint.to_account_info())?;
 
-        let clock = Clock::get()?;
-        let now = clock.unix_timestamp;
-
-        let state = &mut ctx.accounts.oracle_state;
         let (expected_state, bump) = Pubkey::find_program_address(
             &[
                 b"oracle",
@@ -41,28 +50,89 @@ pub mod oracle {
             ],
             ctx.program_id,
         );
-        require_keys_eq!(expected_state, state.key(), OracleError::InvalidPda);
-        state.authority = ctx.accounts.authority.key();
-        state.weight_mint = ctx.accounts.weight_mint.key();
-        state.reward_bps = params.reward_bps;
-        state.half_life_seconds = params.half_life_seconds;
-        state.quorum = params.quorum;
-        state.deposit_locking_period = params.deposit_locking_period;
-        state.withdrawal_locking_period = params.withdrawal_locking_period;
-        state.alpha = params.alpha;
-        state.aggregated_value = 0;
-        state.latest_value = 0;
-        state.aggregated_weight = 0;
-        state.last_submission_time = now;
-        state.last_timestamp = now;
-        state.total_deposited_tokens = 0;
-        state.name = params.name;
-        state.description = params.description;
-        state.price_history = Vec::new();
-        state.targets = Vec::new();
-        state.bump = bump;
+        require_keys_eq!(
+            expected_state,
+            ctx.accounts.oracle_state.key(),
+            OracleError::InvalidPda
+        );
+        init_price_history(
+            &mut ctx.accounts.price_history,
+            ctx.accounts.oracle_state.key(),
+            ctx.bumps.price_history,
+        );
+        init_oracle_state(
+            &mut ctx.accounts.oracle_state,
+            ctx.accounts.authority.key(),
+            ctx.accounts.weight_mint.key(),
+            StakeKind::Token,
+            bump,
+            params,
+        )
+    }
 
-        Ok(())
+    /// Creates an oracle weighted by lamports staked in a vault PDA rather
+    /// than by an SPL token.
+    pub fn initialize_native(
+        ctx: Context<InitializeNativeOracle>,
+        params: InitializeOracleParams,
+    ) -> Result<()> {
+        let rent = Rent::get()?.minimum_balance(0);
+        let vault_lamports = ctx.accounts.native_vault.lamports();
+        if vault_lamports < rent {
+            invoke(
+                &system_instruction::transfer(
+                    &ctx.accounts.payer.key(),
+                    &ctx.accounts.native_vault.key(),
+                    rent - vault_lamports,
+                ),
+                &[
+                    ctx.accounts.payer.to_account_info(),
+                    ctx.accounts.native_vault.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        init_price_history(
+            &mut ctx.accounts.price_history,
+            ctx.accounts.oracle_state.key(),
+            ctx.bumps.price_history,
+        );
+        let state = &mut ctx.accounts.oracle_state;
+        state.native_vault_bump = ctx.bumps.native_vault;
+        init_oracle_state(
+            state,
+            ctx.accounts.authority.key(),
+            NATIVE_WEIGHT_MINT,
+            StakeKind::Native,
+            ctx.bumps.oracle_state,
+            params,
+        )
+    }
+
+    /// Creates an oracle where every verified NFT of `collection_mint`
+    /// deposited by a user counts as one unit of weight.
+    pub fn initialize_nft(
+        ctx: Context<InitializeNftOracle>,
+        params: InitializeOracleParams,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.collection_mint.decimals == 0,
+            OracleError::InvalidCollection
+        );
+        init_price_history(
+            &mut ctx.accounts.price_history,
+            ctx.accounts.oracle_state.key(),
+            ctx.bumps.price_history,
+        );
+        init_oracle_state(
+            &mut ctx.accounts.oracle_state,
+            ctx.accounts.authority.key(),
+            ctx.accounts.collection_mint.key(),
+            StakeKind::NftCollection,
+            ctx.bumps.oracle_state,
+            params,
+        )
     }
 
     pub fn fund(ctx: Context<FundOracle>, amount: u64) -> Result<()> {
@@ -93,50 +163,46 @@ pub mod oracle {
     }
 
     pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64) -> Result<()> {
+        process_deposit(ctx, amount, 0)
+    }
+
+    pub fn deposit_tokens_locked(
+        ctx: Context<DepositTokens>,
+        amount: u64,
+        lock_seconds: u64,
+    ) -> Result<()> {
+        process_deposit(ctx, amount, lock_seconds)
+    }
+
+    pub fn withdraw_tokens(ctx: Context<WithdrawTokens>, amount: u64) -> Result<()> {
         require!(amount > 0, OracleError::AmountMustBePositive);
 
         let now = Clock::get()?.unix_timestamp;
         let state = &mut ctx.accounts.oracle_state;
         let user_state = &mut ctx.accounts.user_state;
 
-        let (expected_user, user_bump) = Pubkey::find_program_address(
-            &[
-                b"user",
-                state.key().as_ref(),
-                ctx.accounts.user.key().as_ref(),
-            ],
-            ctx.program_id,
-        );
-        require_keys_eq!(expected_user, user_state.key(), OracleError::InvalidPda);
-        user_state.ensure_initialized(state.key(), ctx.accounts.user.key(), user_bump)?;
-
-        unlock_tokens_if_possible(state, user_state, now);
+        debit_unlocked(state, user_state, amount, now)?;
 
-        let transfer_accounts = token::Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.oracle_vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        };
-        token::transfer(
-            CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts),
+        burn_receipts(
+            state,
+            &ctx.accounts.receipt_mint,
+            &ctx.accounts.user_receipt_account,
+            &ctx.accounts.user,
+            Some(&ctx.accounts.token_program),
+            amount,
+        )?;
+        transfer_from_vault(
+            state,
+            &ctx.accounts.weight_mint,
+            &ctx.accounts.oracle_vault,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
             amount,
         )?;
 
-        user_state.locked_tokens = user_state 
-            .locked_tokens
-            .checked_add(amount)
-            .ok_or(OracleError::MathOverflow)?;
-
-        state.total_deposited_tokens = state
-            .total_deposited_tokens
-            .checked_add(amount)
-            .ok_or(OracleError::MathOverflow)?;
-
-        user_state.deposit_timestamp = now;
-        user_state.last_operation_timestamp = now;
         state.last_timestamp = now;
 
-        emit!(TokenDeposited {
+        emit!(TokenWithdrawn {
             user: ctx.accounts.user.key(),
             amount,
         });
@@ -144,63 +210,127 @@ pub mod oracle {
         Ok(())
     }
 
-    pub fn withdraw_tokens(ctx: Context<WithdrawTokens>, amount: u64) -> Result<()> {
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
         require!(amount > 0, OracleError::AmountMustBePositive);
 
         let now = Clock::get()?.unix_timestamp;
         let state = &mut ctx.accounts.oracle_state;
         let user_state = &mut ctx.accounts.user_state;
 
-        unlock_tokens_if_possible(state, user_state, now);
+        unlock_tokens_if_possible(user_state, now);
+        require!(now >= user_state.ve_lock_end, OracleError::VeLockActive);
+        require!(user_state.open_disputes == 0, OracleError::StakeUnderDispute);
         require!(
             user_state.unlocked_tokens >= amount,
             OracleError::InsufficientUnlockedTokens
         );
+        require!(
+            user_state.pending_withdrawals.len() < MAX_PENDING_WITHDRAWALS,
+            OracleError::TooManyPendingWithdrawals
+        );
 
-        let unlock_ready = user_state
-            .last_operation_timestamp
-            .checked_add(state.withdrawal_locking_period as i64)
-            .ok_or(OracleError::MathOverflow)?;
-        require!(now >= unlock_ready, OracleError::WithdrawalLocked);
+        burn_receipts(
+            state,
+            &ctx.accounts.receipt_mint,
+            &ctx.accounts.user_receipt_account,
+            &ctx.accounts.user,
+            ctx.accounts.token_program.as_ref(),
+            amount,
+        )?;
 
+        let available_at = now.saturating_add(state.withdrawal_locking_period as i64);
         user_state.unlocked_tokens = user_state
             .unlocked_tokens
             .checked_sub(amount)
             .ok_or(OracleError::MathUnderflow)?;
+        user_state
+            .pending_withdrawals
+            .push(PendingWithdrawal { amount, available_at });
         state.total_deposited_tokens = state
             .total_deposited_tokens
             .checked_sub(amount)
             .ok_or(OracleError::MathUnderflow)?;
 
-        apply_new_weight_to_votes(state, user_state, user_state.unlocked_tokens)?;
+        sync_vote_weight(state, user_state, now)?;
+        state.last_timestamp = now;
 
-        let bump_seed = &[state.bump];
-        let signer_seeds: &[&[u8]] = &[
-            b"oracle",
-            state.authority.as_ref(),
-            state.weight_mint.as_ref(),
-            bump_seed,
-        ];
+        emit!(WithdrawalRequested {
+            user: ctx.accounts.user.key(),
+            amount,
+            available_at,
+        });
 
-        {
-            let authority_info = state.to_account_info();
-            let transfer_accounts = token::Transfer {
-                from: ctx.accounts.oracle_vault.to_account_info(),
-                to: ctx.accounts.user_token_account.to_account_info(),
-                authority: authority_info.clone(),
-            };
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    transfer_accounts,
-                    &[signer_seeds],
-                ),
-                amount,
+        Ok(())
+    }
+
+    pub fn withdraw_early(ctx: Context<WithdrawEarly>, amount: u64) -> Result<()> {
+        require!(amount > 0, OracleError::AmountMustBePositive);
+
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
+
+        let penalty = debit_early(state, user_state, amount, now)?;
+        let payout = amount - penalty;
+
+        burn_receipts(
+            state,
+            &ctx.accounts.receipt_mint,
+            &ctx.accounts.user_receipt_account,
+            &ctx.accounts.user,
+            Some(&ctx.accounts.token_program),
+            amount,
+        )?;
+        if payout > 0 {
+            transfer_from_vault(
+                state,
+                &ctx.accounts.weight_mint,
+                &ctx.accounts.oracle_vault,
+                &ctx.accounts.user_token_account,
+                &ctx.accounts.token_program,
+                payout,
+            )?;
+        }
+        if penalty > 0 {
+            transfer_from_vault(
+                state,
+                &ctx.accounts.weight_mint,
+                &ctx.accounts.oracle_vault,
+                &ctx.accounts.treasury_token_account,
+                &ctx.accounts.token_program,
+                penalty,
             )?;
         }
 
         state.last_timestamp = now;
 
+        emit!(EarlyWithdrawal {
+            user: ctx.accounts.user.key(),
+            amount,
+            penalty,
+        });
+
+        Ok(())
+    }
+
+    pub fn complete_withdrawal(ctx: Context<WithdrawTokens>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
+
+        let amount = take_matured_withdrawals(user_state, now)?;
+
+        transfer_from_vault(
+            state,
+            &ctx.accounts.weight_mint,
+            &ctx.accounts.oracle_vault,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+
+        state.last_timestamp = now;
+
         emit!(TokenWithdrawn {
             user: ctx.accounts.user.key(),
             amount,
@@ -209,72 +339,306 @@ pub mod oracle {
         Ok(())
     }
 
-    pub fn submit_value(ctx: Context<SubmitValue>, new_value: i128) -> Result<()> {
+    pub fn deposit_native(ctx: Context<DepositNative>, amount: u64) -> Result<()> {
+        process_native_deposit(ctx, amount, 0)
+    }
+
+    pub fn deposit_native_locked(
+        ctx: Context<DepositNative>,
+        amount: u64,
+        lock_seconds: u64,
+    ) -> Result<()> {
+        process_native_deposit(ctx, amount, lock_seconds)
+    }
+
+    pub fn withdraw_native(ctx: Context<WithdrawNative>, amount: u64) -> Result<()> {
+        require!(amount > 0, OracleError::AmountMustBePositive);
+
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
+
+        debit_unlocked(state, user_state, amount, now)?;
+
+        transfer_from_native_vault(
+            state,
+            &ctx.accounts.native_vault,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+
+        state.last_timestamp = now;
+
+        emit!(TokenWithdrawn {
+            user: ctx.accounts.user.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn withdraw_early_native(ctx: Context<WithdrawEarlyNative>, amount: u64) -> Result<()> {
+        require!(amount > 0, OracleError::AmountMustBePositive);
+
+        let now = Clock::get()?.unix_timestamp;
         let state = &mut ctx.accounts.oracle_state;
         let user_state = &mut ctx.accounts.user_state;
+
+        let penalty = debit_early(state, user_state, amount, now)?;
+        let payout = amount - penalty;
+
+        if payout > 0 {
+            transfer_from_native_vault(
+                state,
+                &ctx.accounts.native_vault,
+                &ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program,
+                payout,
+            )?;
+        }
+        if penalty > 0 {
+            transfer_from_native_vault(
+                state,
+                &ctx.accounts.native_vault,
+                &ctx.accounts.treasury.to_account_info(),
+                &ctx.accounts.system_program,
+                penalty,
+            )?;
+        }
+
+        state.last_timestamp = now;
+
+        emit!(EarlyWithdrawal {
+            user: ctx.accounts.user.key(),
+            amount,
+            penalty,
+        });
+
+        Ok(())
+    }
+
+    pub fn complete_withdrawal_native(ctx: Context<WithdrawNative>) -> Result<()> {
         let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
 
+        let amount = take_matured_withdrawals(user_state, now)?;
+
+        transfer_from_native_vault(
+            state,
+            &ctx.accounts.native_vault,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+
+        state.last_timestamp = now;
+
+        emit!(TokenWithdrawn {
+            user: ctx.accounts.user.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_nft(ctx: Context<DepositNft>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let nft_mint = &ctx.accounts.nft_mint;
         require!(
-            !state.is_blacklisted(&ctx.accounts.user.key()),
-            OracleError::AccountBlacklisted
+            nft_mint.decimals == 0 && nft_mint.supply == 1,
+            OracleError::InvalidCollection
+        );
+        let collection = metadata::verified_collection(
+            &ctx.accounts.nft_metadata.try_borrow_data()?,
+            &nft_mint.key(),
         );
-
-        unlock_tokens_if_possible(state, user_state, now);
         require!(
-            user_state.unlocked_tokens > 0,
-            OracleError::NoUnlockedTokens
+            collection == Some(ctx.accounts.oracle_state.weight_mint),
+            OracleError::InvalidCollection
         );
 
-        let weight = u128::from(user_state.unlocked_tokens);
-        let elapsed_global = time_difference(now, state.last_submission_time);
-        let decayed_q =
-            decay::apply_decay(state.aggregated_weight, elapsed_global, state.half_life_seconds)?;
+        let transfer_accounts = token_interface::TransferChecked {
+            from: ctx.accounts.user_nft_account.to_account_info(),
+            mint: nft_mint.to_account_info(),
+            to: ctx.accounts.nft_escrow.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts),
+            1,
+            0,
+        )?;
 
-        let time_since_user = time_difference(now, user_state.last_submission_time);
-        let old_weight = u128::from(user_state.weight);
-        let user_weight_decayed =
-            decay::apply_decay(old_weight, time_since_user, state.half_life_seconds)?;
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
+        user_state.ensure_initialized(state.key(), ctx.accounts.user.key(), ctx.bumps.user_state)?;
+        unlock_tokens_if_possible(user_state, now);
+        record_deposit(state, user_state, 1, now)?;
+        user_state.nft_count = user_state
+            .nft_count
+            .checked_add(1)
+            .ok_or(OracleError::MathOverflow)?;
 
-        let decayed_q_i128 = to_i128(decayed_q)?;
-        let user_weight_decayed_i128 = to_i128(user_weight_decayed)?;
-        let weight_i128 = to_i128(weight)?;
+        let nft_lock = &mut ctx.accounts.nft_lock;
+        nft_lock.unlock_time = now.saturating_add(state.deposit_locking_period as i64);
+        nft_lock.bump = ctx.bumps.nft_lock;
 
-        let mut numerator = state
-            .aggregated_value
-            .checked_mul(decayed_q_i128)
-            .ok_or(OracleError::MathOverflow)?;
+        emit!(NftDeposited {
+            user: ctx.accounts.user.key(),
+            mint: nft_mint.key(),
+        });
 
-        if user_weight_decayed > 0 {
-            numerator = numerator
-                .checked_sub(
-                    user_state
-                        .last_submitted_price
-                        .checked_mul(user_weight_decayed_i128)
-                        .ok_or(OracleError::MathOverflow)?,
-                )
-                .ok_or(OracleError::MathUnderflow)?;
-        }
+        Ok(())
+    }
 
-        let new_q = decayed_q
-            .checked_sub(user_weight_decayed)
-            .ok_or(OracleError::MathUnderflow)?
-            .checked_add(weight)
-            .ok_or(OracleError::MathOverflow)?;
-        require!(new_q > 0, OracleError::ZeroWeightAfterUpdate);
-
-        numerator = numerator
-            .checked_add(
-                new_value
-                    .checked_mul(weight_i128)
-                    .ok_or(OracleError::MathOverflow)?,
-            )
-            .ok_or(OracleError::MathOverflow)?;
+    pub fn withdraw_nft(ctx: Context<WithdrawNft>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
 
-        let new_p = numerator
-            .checked_div(to_i128(new_q)?)
+        // Weight units are fungible, so the lock is checked on this NFT
+        // rather than on the user's unlocked count.
+        require!(
+            now >= ctx.accounts.nft_lock.unlock_time,
+            OracleError::WithdrawalLocked
+        );
+        debit_unlocked(state, user_state, 1, now)?;
+        user_state.nft_count = user_state
+            .nft_count
+            .checked_sub(1)
             .ok_or(OracleError::MathUnderflow)?;
 
-        let balance = state.to_account_info().lamports();
+        transfer_from_vault(
+            state,
+            &ctx.accounts.nft_mint,
+            &ctx.accounts.nft_escrow,
+            &ctx.accounts.user_nft_account,
+            &ctx.accounts.token_program,
+            1,
+        )?;
+
+        let bump_seed = &[state.bump];
+        let signer_seeds: &[&[u8]] = &state.signer_seeds(bump_seed);
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.nft_escrow.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: state.to_account_info(),
+            },
+            &[signer_seeds],
+        ))?;
+
+        state.last_timestamp = now;
+
+        emit!(NftWithdrawn {
+            user: ctx.accounts.user.key(),
+            mint: ctx.accounts.nft_mint.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn submit_value(ctx: Context<SubmitValue>, new_value: i128) -> Result<()> {
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
+        let history = &mut ctx.accounts.price_history;
+        let now = Clock::get()?.unix_timestamp;
+
+        let owner = user_state.owner;
+        require!(
+            !state.is_blacklisted(&owner) && !state.is_blacklisted(&ctx.accounts.user.key()),
+            OracleError::AccountBlacklisted
+        );
+        require!(state.status != OracleStatus::Halted, OracleError::OracleHalted);
+        require!(
+            state.access_mode == AccessMode::Open || ctx.accounts.submitter_permit.is_some(),
+            OracleError::SubmitterNotPermitted
+        );
+        require!(
+            time_difference(now, user_state.last_submission_time) >= state.min_submission_interval,
+            OracleError::SubmissionTooFrequent
+        );
+
+        sync_vote_weight(state, user_state, now)?;
+        require!(user_state.has_stake(), OracleError::NoUnlockedTokens);
+        require!(
+            user_state.staked_tokens() >= state.min_submit_stake,
+            OracleError::StakeBelowSubmitMinimum
+        );
+
+        let boosted = boosted_weight(state, user_state, now)?;
+        let capped = boosted.min(state.weight_cap());
+        let effective = state.weighting_mode.apply(capped);
+        let weight = u128::from(effective);
+        let time_since_user = time_difference(now, user_state.last_submission_time);
+        let previous_record = history.live_record(user_state);
+        let (decayed_sum, decayed_q, user_weight_decayed) = if state.decay_model.is_windowed() {
+            // Each contribution fades by its own age, so the aggregate is
+            // rebuilt from the history rather than decayed as a whole.
+            let (sum, total) = accumulator::windowed(
+                history.contributions(now),
+                state.decay_model,
+                state.half_life_seconds,
+            )?;
+            let previous = match previous_record {
+                Some(_) => decay::apply_decay(
+                    u128::from(user_state.weight),
+                    time_since_user,
+                    state.decay_model,
+                    state.half_life_seconds,
+                )?,
+                None => 0,
+            };
+            (sum, total, previous)
+        } else {
+            let elapsed_global = time_difference(now, state.last_submission_time);
+            let decayed_q = decay::apply_decay(
+                state.aggregated_weight,
+                elapsed_global,
+                state.decay_model,
+                state.half_life_seconds,
+            )?;
+            let user_weight_decayed = decay::apply_decay(
+                u128::from(user_state.weight),
+                time_since_user,
+                state.decay_model,
+                state.half_life_seconds,
+            )?;
+            let sum_factor =
+                decay::factor(state.decay_model, elapsed_global, state.half_life_seconds);
+            let decayed_sum = accumulator::decay(state.weighted_sum, sum_factor)?;
+            (decayed_sum, decayed_q, user_weight_decayed)
+        };
+
+        let (new_sum, new_q, new_p) = accumulator::submit(
+            decayed_sum,
+            decayed_q,
+            user_state.last_submitted_price,
+            user_weight_decayed,
+            new_value,
+            weight,
+        )?;
+
+        if let Some(reference_value) = state.breached_deviation(history, new_p, now) {
+            state.status = OracleStatus::Halted;
+            state.last_timestamp = now;
+            emit!(CircuitBreakerTripped {
+                oracle: state.key(),
+                submitter: owner,
+                reference_value,
+                rejected_value: new_p,
+                timestamp: now,
+            });
+            return Ok(());
+        }
+
+        let balance = state
+            .to_account_info()
+            .lamports()
+            .saturating_sub(state.delegator_rewards_reserved);
         let reward_pool = (u128::from(balance)
             .checked_mul(u128::from(state.reward_bps))
             .ok_or(OracleError::MathOverflow)?)
@@ -282,65 +646,77 @@ pub mod oracle {
 
         let reward = decay::calculate_reward(
             reward_pool,
-            user_state.unlocked_tokens,
+            effective,
             time_since_user,
             decayed_q,
             state.alpha,
+            state.decay_model,
             state.half_life_seconds,
         )?;
 
-        if reward > 0 {
-            let state_info = state.to_account_info();
-            let user_info = ctx.accounts.user.to_account_info();
-
-            **state_info.try_borrow_mut_lamports()? = state_info
-                .lamports()
-                .checked_sub(reward)
-                .ok_or(OracleError::MathUnderflow)?;
-            **user_info.try_borrow_mut_lamports()? = user_info
-                .lamports()
-                .checked_add(reward)
+        let delegator_share = delegator_reward_share(user_state, reward)?;
+        if delegator_share > 0 {
+            let per_token = u128::from(delegator_share) * WAD / u128::from(user_state.delegated_in);
+            user_state.delegation_reward_index = user_state
+                .delegation_reward_index
+                .checked_add(per_token)
+                .ok_or(OracleError::MathOverflow)?;
+            state.delegator_rewards_reserved = state
+                .delegator_rewards_reserved
+                .checked_add(delegator_share)
                 .ok_or(OracleError::MathOverflow)?;
         }
 
+        let payout = reward - delegator_share;
+        if payout > 0 {
+            pay_from_oracle(
+                &state.to_account_info(),
+                &ctx.accounts.owner.to_account_info(),
+                payout,
+            )?;
+        }
+
+        state.weighted_sum = new_sum;
         state.aggregated_value = new_p;
         state.latest_value = new_value;
         state.aggregated_weight = new_q;
         state.last_submission_time = now;
         state.last_timestamp = now;
+        state.status = OracleStatus::Active;
 
         user_state.last_submitted_price = new_value;
-        user_state.weight = user_state.unlocked_tokens;
+        user_state.weight = effective;
         user_state.last_submission_time = now;
         user_state.last_operation_timestamp = now;
-
-        push_price_history(state, now, new_p, new_value)?;
+        if let Some(idx) = previous_record {
+            history.records[idx].superseded = true;
+        }
+        let sequence = push_price_history(history, now, new_p, new_value, effective)?;
+        user_state.record_submission(sequence, now, new_value);
 
         emit!(ValueSubmitted {
-            submitter: ctx.accounts.user.key(),
+            submitter: owner,
             timestamp: now,
             submitted_value: new_value,
             aggregated_value: new_p,
-            weight: user_state.unlocked_tokens,
+            weight: effective,
+            capped_weight: boosted - capped,
             reward_lamports: reward,
         });
 
         Ok(())
     }
 
+    /// Re-syncs a staker's vote weight. Anyone may call it, so weight that
+    /// changed without an instruction from the staker, such as matured lots,
+    /// a decaying vote-escrow boost or a moved weight cap, reaches
+    /// `total_vote_weight` and later snapshots.
     pub fn update_user_vote_weights(ctx: Context<UpdateUserVoteWeights>) -> Result<()> {
         let now = Clock::get()?.unix_timestamp;
         let state = &mut ctx.accounts.oracle_state;
         let user_state = &mut ctx.accounts.user_state;
 
-        unlock_tokens_if_possible(state, user_state, now);
-        require!(
-            user_state.unlocked_tokens > 0,
-            OracleError::NoUnlockedTokens
-        );
-
-        apply_new_weight_to_votes(state, user_state, user_state.unlocked_tokens)?;
-        user_state.last_operation_timestamp = now;
+        sync_vote_weight(state, user_state, now)?;
         state.last_timestamp = now;
 
         Ok(())
@@ -353,498 +729,3776 @@ pub mod oracle {
     pub fn vote_whitelist(ctx: Context<Vote>, target: Pubkey) -> Result<()> {
         process_vote(ctx, target, VoteKind::Whitelist)
     }
-}
 
-fn process_vote(ctx: Context<Vote>, target: Pubkey, kind: VoteKind) -> Result<()> {
-    let now = Clock::get()?.unix_timestamp;
-    let state = &mut ctx.accounts.oracle_state;
-    let voter_state = &mut ctx.accounts.user_state;
+    pub fn set_commission(ctx: Context<UpdateUserSettings>, commission_bps: u64) -> Result<()> {
+        require!(commission_bps <= DENOMINATOR, OracleError::InvalidCommission);
+        ctx.accounts.user_state.commission_bps = commission_bps;
+        Ok(())
+    }
 
-    require!(
-        !state.is_blacklisted(&ctx.accounts.user.key()),
-        OracleError::AccountBlacklisted
-    );
+    /// Lets `hot_key` submit values and vote for the staker. Pass the default
+    /// pubkey to revoke. Deposits and withdrawals still need the owner.
+    pub fn set_submitter(ctx: Context<UpdateUserSettings>, hot_key: Pubkey) -> Result<()> {
+        let user_state = &mut ctx.accounts.user_state;
+        user_state.submitter = hot_key;
 
-    unlock_tokens_if_possible(state, voter_state, now);
-    let weight = voter_state.unlocked_tokens;
-    require!(weight > 0, OracleError::NoUnlockedTokens);
+        emit!(SubmitterChanged {
+            owner: user_state.owner,
+            submitter: hot_key,
+        });
 
-    match kind {
-        VoteKind::Blacklist => {
-            require!(
-                voter_state
-                    .blacklist_votes
-                    .iter()
-                    .all(|vote| vote.target != target),
-                OracleError::AlreadyVoted
-            );
-            require!(
-                voter_state.blacklist_votes.len() < MAX_USER_VOTES,
-                OracleError::TooManyVotes
-            );
-            let idx = state.upsert_target(target)?;
-            state.targets[idx].blacklist_votes = state.targets[idx]
-                .blacklist_votes
-                .checked_add(weight)
-                .ok_or(OracleError::MathOverflow)?;
-            let changed = state.refresh_blacklist_status(idx);
-            voter_state.blacklist_votes.push(UserVote { target, weight });
-            if changed {
-                emit!(BlacklistStatusChanged {
-                    target,
-                    is_blacklisted: state.targets[idx].is_blacklisted,
-                });
-            }
-        }
-        VoteKind::Whitelist => {
-            require!(
-                voter_state
-                    .whitelist_votes
-                    .iter()
-                    .all(|vote| vote.target != target),
-                OracleError::AlreadyVoted
-            );
-            require!(
-                voter_state.whitelist_votes.len() < MAX_USER_VOTES,
-                OracleError::TooManyVotes
-            );
-            let idx = state.upsert_target(target)?;
-            state.targets[idx].whitelist_votes = state.targets[idx]
-                .whitelist_votes
-                .checked_add(weight)
-                .ok_or(OracleError::MathOverflow)?;
-            let changed = state.refresh_blacklist_status(idx);
-            voter_state.whitelist_votes.push(UserVote { target, weight });
-            if changed {
-                emit!(BlacklistStatusChanged {
-                    target,
-                    is_blacklisted: state.targets[idx].is_blacklisted,
-                });
-            }
-        }
+        Ok(())
     }
 
-    voter_state.last_operation_timestamp = now;
-    state.last_timestamp = now;
+    pub fn delegate(ctx: Context<Delegate>, to: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let user = ctx.accounts.user.key();
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
+        let delegate_state = &mut ctx.accounts.delegate_state;
 
-    emit!(Voted {
-        target,
-        voter: ctx.accounts.user.key(),
-        is_blacklist: matches!(kind, VoteKind::Blacklist),
-        weight,
-    });
+        require!(to != user, OracleError::SelfDelegation);
+        require!(!state.is_blacklisted(&user), OracleError::AccountBlacklisted);
+        require!(!state.is_blacklisted(&to), OracleError::AccountBlacklisted);
 
-    Ok(())
-}
+        require!(user_state.open_disputes == 0, OracleError::StakeUnderDispute);
+        unlock_tokens_if_possible(user_state, now);
+        let amount = user_state.unlocked_tokens;
+        require!(amount > 0, OracleError::NoUnlockedTokens);
 
-fn unlock_tokens_if_possible(state: &Account<OracleState>, user_state: &mut Account<UserState>, now: i64) {
-    if user_state.locked_tokens == 0 {
-        return;
+        user_state.unlocked_tokens = 0;
+        user_state.delegated_out = amount;
+        delegate_state.delegated_in = delegate_state
+            .delegated_in
+            .checked_add(amount)
+            .ok_or(OracleError::MathOverflow)?;
+
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.oracle = state.key();
+        delegation.delegator = user;
+        delegation.delegate = to;
+        delegation.amount = amount;
+        delegation.reward_index = delegate_state.delegation_reward_index;
+        delegation.available_at = 0;
+        delegation.bump = ctx.bumps.delegation;
+
+        sync_vote_weight(state, user_state, now)?;
+        sync_vote_weight(state, delegate_state, now)?;
+
+        user_state.last_operation_timestamp = now;
+        state.last_timestamp = now;
+
+        emit!(Delegated {
+            delegator: user,
+            delegate: to,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn undelegate(ctx: Context<Undelegate>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let delegate_state = &mut ctx.accounts.delegate_state;
+        let delegation = &mut ctx.accounts.delegation;
+
+        require!(delegation.available_at == 0, OracleError::UndelegationPending);
+
+        settle_delegation_reward(
+            state,
+            delegation,
+            delegate_state.delegation_reward_index,
+            &ctx.accounts.user.to_account_info(),
+        )?;
+
+        delegate_state.delegated_in = delegate_state
+            .delegated_in
+            .checked_sub(delegation.amount)
+            .ok_or(OracleError::MathUnderflow)?;
+        sync_vote_weight(state, delegate_state, now)?;
+
+        delegation.available_at = now.saturating_add(state.withdrawal_locking_period as i64);
+        state.last_timestamp = now;
+
+        emit!(Undelegated {
+            delegator: delegation.delegator,
+            delegate: delegation.delegate,
+            amount: delegation.amount,
+            available_at: delegation.available_at,
+        });
+
+        Ok(())
     }
-    let unlock_ready = user_state
-        .deposit_timestamp
-        .saturating_add(state.deposit_locking_period as i64);
-    if now >= unlock_ready {
+
+    pub fn complete_undelegation(ctx: Context<CompleteUndelegation>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
+        let delegation = &ctx.accounts.delegation;
+
+        require!(delegation.available_at != 0, OracleError::DelegationActive);
+        require!(
+            now >= delegation.available_at,
+            OracleError::UndelegationLocked
+        );
+        require!(user_state.open_disputes == 0, OracleError::StakeUnderDispute);
+
+        unlock_tokens_if_possible(user_state, now);
         user_state.unlocked_tokens = user_state
             .unlocked_tokens
-            .saturating_add(user_state.locked_tokens);
-        user_state.locked_tokens = 0;
+            .checked_add(delegation.amount)
+            .ok_or(OracleError::MathOverflow)?;
+        user_state.delegated_out = 0;
+        sync_vote_weight(state, user_state, now)?;
+
+        user_state.last_operation_timestamp = now;
+        state.last_timestamp = now;
+
+        Ok(())
     }
-}
 
-fn apply_new_weight_to_votes(
-    state: &mut Account<OracleState>,
-    user_state: &mut Account<UserState>,
-    new_weight: u64,
-) -> Result<()> {
-    for vote in user_state.blacklist_votes.iter_mut() {
-        let idx = state.upsert_target(vote.target)?;
-        state.targets[idx].blacklist_votes = state.targets[idx]
-            .blacklist_votes
-            .checked_sub(vote.weight)
-            .ok_or(OracleError::MathUnderflow)?
-            .checked_add(new_weight)
-            .ok_or(OracleError::MathOverflow)?;
-        vote.weight = new_weight;
-        if state.refresh_blacklist_status(idx) {
-            emit!(BlacklistStatusChanged {
-                target: vote.target,
-                is_blacklisted: state.targets[idx].is_blacklisted,
+    pub fn claim_delegation_rewards(ctx: Context<Undelegate>) -> Result<()> {
+        let state = &mut ctx.accounts.oracle_state;
+        let delegation = &mut ctx.accounts.delegation;
+
+        require!(delegation.available_at == 0, OracleError::UndelegationPending);
+
+        let amount = settle_delegation_reward(
+            state,
+            delegation,
+            ctx.accounts.delegate_state.delegation_reward_index,
+            &ctx.accounts.user.to_account_info(),
+        )?;
+
+        emit!(DelegationRewardClaimed {
+            delegator: delegation.delegator,
+            delegate: delegation.delegate,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn poke(ctx: Context<Poke>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+
+        require!(state.heartbeat_seconds > 0, OracleError::HeartbeatDisabled);
+        let deadline = state
+            .last_submission_time
+            .saturating_add(state.heartbeat_seconds as i64);
+        require!(now > deadline, OracleError::HeartbeatNotMissed);
+
+        if state.status == OracleStatus::Active {
+            state.status = OracleStatus::Stale;
+            state.last_timestamp = now;
+            emit!(OracleStale {
+                oracle: state.key(),
+                last_submission_time: state.last_submission_time,
+                timestamp: now,
             });
         }
+
+        Ok(())
     }
 
-    for vote in user_state.whitelist_votes.iter_mut() {
-        let idx = state.upsert_target(vote.target)?;
-        state.targets[idx].whitelist_votes = state.targets[idx]
-            .whitelist_votes
-            .checked_sub(vote.weight)
-            .ok_or(OracleError::MathUnderflow)?
-            .checked_add(new_weight)
-            .ok_or(OracleError::MathOverflow)?;
-        vote.weight = new_weight;
-        if state.refresh_blacklist_status(idx) {
+    /// Re-applies the blacklist rule to `target`, e.g. after stake moved a
+    /// percentage quorum.
+    pub fn refresh_target(ctx: Context<Poke>, target: Pubkey) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+
+        let idx = state
+            .targets
+            .iter()
+            .position(|record| record.target == target)
+            .ok_or(OracleError::UnknownTarget)?;
+        if state.refresh_blacklist_status(idx, now, clock.slot) {
             emit!(BlacklistStatusChanged {
-                target: vote.target,
+                target,
                 is_blacklisted: state.targets[idx].is_blacklisted,
             });
         }
+
+        Ok(())
     }
 
-    Ok(())
-}
+    pub fn reset_circuit_breaker(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
 
-fn push_price_history(
-    state: &mut Account<OracleState>,
-    timestamp: i64,
-    aggregated_value: i128,
-    latest_value: i128,
-) -> Result<()> {
-    if state.price_history.len() == MAX_HISTORY_ENTRIES {
-        state.price_history.remove(0);
+        require!(state.status == OracleStatus::Halted, OracleError::OracleNotHalted);
+        state.status = OracleStatus::Active;
+        state.breaker_reset_time = now;
+        state.last_timestamp = now;
+
+        emit!(CircuitBreakerReset {
+            oracle: state.key(),
+            timestamp: now,
+        });
+
+        Ok(())
     }
-    state.price_history.push(PriceRecord {
-        timestamp,
-        aggregated_value,
-        latest_value,
-    });
-    Ok(())
-}
 
-fn time_difference(now: i64, previous: i64) -> u64 {
-    if now <= previous {
-        0
-    } else {
-        (now - previous) as u64
+    pub fn grant_submitter(ctx: Context<GrantSubmitter>, submitter: Pubkey) -> Result<()> {
+        let permit = &mut ctx.accounts.submitter_permit;
+        permit.oracle = ctx.accounts.oracle_state.key();
+        permit.submitter = submitter;
+        permit.payer = ctx.accounts.payer.key();
+        permit.via_vote = false;
+        permit.bump = ctx.bumps.submitter_permit;
+
+        emit!(SubmitterPermitGranted {
+            oracle: permit.oracle,
+            submitter,
+            via_vote: false,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_submitter_permit(ctx: Context<ClaimSubmitterPermit>) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let submitter = ctx.accounts.user.key();
+        require!(
+            ctx.accounts.oracle_state.is_whitelisted(&submitter, slot),
+            OracleError::NotWhitelisted
+        );
+
+        let permit = &mut ctx.accounts.submitter_permit;
+        permit.oracle = ctx.accounts.oracle_state.key();
+        permit.submitter = submitter;
+        permit.payer = submitter;
+        permit.via_vote = true;
+        permit.bump = ctx.bumps.submitter_permit;
+
+        emit!(SubmitterPermitGranted {
+            oracle: permit.oracle,
+            submitter,
+            via_vote: true,
+        });
+
+        Ok(())
+    }
+
+    pub fn revoke_submitter(ctx: Context<RevokeSubmitter>) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let state = &ctx.accounts.oracle_state;
+        let permit = &ctx.accounts.submitter_permit;
+
+        // Vote-granted permits lapse once the whitelist vote no longer holds.
+        require!(
+            ctx.accounts.caller.key() == state.authority
+                || (permit.via_vote && !state.is_whitelisted(&permit.submitter, slot)),
+            OracleError::UnauthorizedRevocation
+        );
+
+        emit!(SubmitterPermitRevoked {
+            oracle: state.key(),
+            submitter: permit.submitter,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_id: u64,
+        update: ParameterUpdate,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let user_state = &mut ctx.accounts.user_state;
+
+        require!(state.proposal_voting_period > 0, OracleError::GovernanceDisabled);
+        require!(proposal_id == state.proposal_count, OracleError::InvalidProposalId);
+        require!(
+            !state.is_blacklisted(&user_state.owner),
+            OracleError::AccountBlacklisted
+        );
+        sync_vote_weight(state, user_state, now)?;
+        require!(user_state.has_stake(), OracleError::NoUnlockedTokens);
+        require!(
+            user_state.vote_weight >= state.min_vote_stake,
+            OracleError::StakeBelowVoteMinimum
+        );
+        update.validate()?;
+
+        state.proposal_count = state
+            .proposal_count
+            .checked_add(1)
+            .ok_or(OracleError::MathOverflow)?;
+        let voting_ends_at = now.saturating_add(state.proposal_voting_period as i64);
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.oracle = state.key();
+        proposal.id = proposal_id;
+        proposal.proposer = user_state.owner;
+        proposal.update = update;
+        proposal.snapshot_slot = Clock::get()?.slot;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.voting_ends_at = voting_ends_at;
+        proposal.executable_at =
+            voting_ends_at.saturating_add(state.proposal_execution_delay as i64);
+        proposal.expires_at = proposal
+            .executable_at
+            .saturating_add(state.proposal_execution_grace as i64);
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        emit!(ProposalCreated {
+            oracle: state.key(),
+            proposal: proposal.key(),
+            id: proposal_id,
+            proposer: proposal.proposer,
+            voting_ends_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn vote_proposal(ctx: Context<VoteProposal>, support: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &ctx.accounts.oracle_state;
+        let user_state = &ctx.accounts.user_state;
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(now < proposal.voting_ends_at, OracleError::ProposalVotingClosed);
+        require!(
+            !state.is_blacklisted(&user_state.owner),
+            OracleError::AccountBlacklisted
+        );
+        let weight = user_state.weight_before(proposal.snapshot_slot);
+        require!(weight > 0, OracleError::NoStakeAtSnapshot);
+        require!(weight >= state.min_vote_stake, OracleError::StakeBelowVoteMinimum);
+
+        let tally = if support {
+            &mut proposal.yes_votes
+        } else {
+            &mut proposal.no_votes
+        };
+        *tally = tally.checked_add(weight).ok_or(OracleError::MathOverflow)?;
+
+        let vote = &mut ctx.accounts.proposal_vote;
+        vote.proposal = proposal.key();
+        vote.voter = user_state.owner;
+        vote.support = support;
+        vote.weight = weight;
+        vote.bump = ctx.bumps.proposal_vote;
+
+        emit!(ProposalVoted {
+            proposal: proposal.key(),
+            voter: vote.voter,
+            support,
+            weight,
+        });
+
+        Ok(())
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(!proposal.executed, OracleError::ProposalAlreadyExecuted);
+        require!(
+            now >= proposal.voting_ends_at && now >= proposal.executable_at,
+            OracleError::ProposalNotExecutable
+        );
+        require!(now < proposal.expires_at, OracleError::ProposalExpired);
+        require!(
+            proposal.passed(state.quorum_threshold(clock.slot)),
+            OracleError::ProposalRejected
+        );
+
+        proposal.executed = true;
+        proposal.update.apply(state, now)?;
+        state.last_timestamp = now;
+
+        emit!(ProposalExecuted {
+            oracle: state.key(),
+            proposal: proposal.key(),
+            id: proposal.id,
+        });
+
+        Ok(())
+    }
+
+    pub fn open_appeal(ctx: Context<OpenAppeal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &ctx.accounts.oracle_state;
+        let appellant = ctx.accounts.appellant.key();
+
+        require!(state.appeal_voting_period > 0, OracleError::AppealsDisabled);
+        let blacklisted_at = state
+            .blacklisted_at(&appellant)
+            .ok_or(OracleError::NotBlacklisted)?;
+        require!(
+            time_difference(now, blacklisted_at) >= state.min_blacklist_duration,
+            OracleError::AppealTooEarly
+        );
+
+        let bond = state.appeal_bond;
+        if bond > 0 {
+            invoke(
+                &system_instruction::transfer(&appellant, &ctx.accounts.appeal.key(), bond),
+                &[
+                    ctx.accounts.appellant.to_account_info(),
+                    ctx.accounts.appeal.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        let voting_ends_at = now.saturating_add(state.appeal_voting_period as i64);
+        let appeal = &mut ctx.accounts.appeal;
+        appeal.oracle = state.key();
+        appeal.appellant = appellant;
+        appeal.bond = bond;
+        appeal.snapshot_slot = Clock::get()?.slot;
+        appeal.support_votes = 0;
+        appeal.oppose_votes = 0;
+        appeal.voting_ends_at = voting_ends_at;
+        appeal.bump = ctx.bumps.appeal;
+
+        emit!(AppealOpened {
+            oracle: state.key(),
+            appellant,
+            bond,
+            voting_ends_at,
+        });
+
+        Ok(())
     }
+
+    pub fn vote_appeal(ctx: Context<VoteAppeal>, support: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &ctx.accounts.oracle_state;
+        let user_state = &ctx.accounts.user_state;
+        let appeal = &mut ctx.accounts.appeal;
+        let appeal_vote = &mut ctx.accounts.appeal_vote;
+
+        require!(now < appeal.voting_ends_at, OracleError::AppealVotingClosed);
+        require!(
+            user_state.owner != appeal.appellant,
+            OracleError::AppellantCannotVote
+        );
+        require!(
+            !state.is_blacklisted(&user_state.owner),
+            OracleError::AccountBlacklisted
+        );
+        // The vote record outlives a resolved appeal, so one from an earlier
+        // round of the same appellant is simply overwritten.
+        require!(
+            appeal_vote.voting_ends_at != appeal.voting_ends_at,
+            OracleError::AlreadyVoted
+        );
+
+        let weight = user_state.weight_before(appeal.snapshot_slot);
+        require!(weight > 0, OracleError::NoStakeAtSnapshot);
+        require!(weight >= state.min_vote_stake, OracleError::StakeBelowVoteMinimum);
+
+        let tally = if support {
+            &mut appeal.support_votes
+        } else {
+            &mut appeal.oppose_votes
+        };
+        *tally = tally.checked_add(weight).ok_or(OracleError::MathOverflow)?;
+
+        appeal_vote.voter = user_state.owner;
+        appeal_vote.voting_ends_at = appeal.voting_ends_at;
+        appeal_vote.bump = ctx.bumps.appeal_vote;
+
+        emit!(AppealVoted {
+            appellant: appeal.appellant,
+            voter: user_state.owner,
+            support,
+            weight,
+        });
+
+        Ok(())
+    }
+
+    pub fn resolve_appeal(ctx: Context<ResolveAppeal>) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let appeal = &ctx.accounts.appeal;
+
+        require!(now >= appeal.voting_ends_at, OracleError::AppealVotingOpen);
+
+        let granted = appeal.support_votes > appeal.oppose_votes
+            && appeal.support_votes.saturating_add(appeal.oppose_votes)
+                > state.quorum_threshold(clock.slot);
+        let idx = state.upsert_target(appeal.appellant, clock.slot)?;
+        let record = &mut state.targets[idx];
+        if granted {
+            // Votes cast so far are void; only ones cast from now on count,
+            // weighed as of the pardon.
+            record.blacklist_votes = 0;
+            record.snapshot_slot = clock.slot;
+            record.pardon_round = record
+                .pardon_round
+                .checked_add(1)
+                .ok_or(OracleError::MathOverflow)?;
+            if record.is_blacklisted {
+                record.is_blacklisted = false;
+                emit!(BlacklistStatusChanged {
+                    target: appeal.appellant,
+                    is_blacklisted: false,
+                });
+            }
+        } else {
+            // A failed appeal restarts the cooldown and forfeits the bond to
+            // the reward pool; the appeal's rent still goes back on close.
+            record.blacklisted_at = now;
+            let appeal_info = appeal.to_account_info();
+            let state_info = state.to_account_info();
+            **appeal_info.try_borrow_mut_lamports()? = appeal_info
+                .lamports()
+                .checked_sub(appeal.bond)
+                .ok_or(OracleError::MathUnderflow)?;
+            **state_info.try_borrow_mut_lamports()? = state_info
+                .lamports()
+                .checked_add(appeal.bond)
+                .ok_or(OracleError::MathOverflow)?;
+        }
+        state.last_timestamp = now;
+
+        emit!(AppealResolved {
+            oracle: state.key(),
+            appellant: appeal.appellant,
+            granted,
+            support_votes: appeal.support_votes,
+            oppose_votes: appeal.oppose_votes,
+        });
+
+        Ok(())
+    }
+
+    pub fn open_dispute(
+        ctx: Context<OpenDispute>,
+        record_sequence: u64,
+        proposed_value: i128,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let submitter_state = &mut ctx.accounts.submitter_state;
+
+        require!(state.dispute_voting_period > 0, OracleError::DisputesDisabled);
+        let submission = submitter_state
+            .recent_submissions
+            .iter()
+            .find(|submission| submission.sequence == record_sequence)
+            .ok_or(OracleError::SubmissionNotFound)?;
+        let (record_timestamp, disputed_value) = (submission.timestamp, submission.value);
+        require!(
+            proposed_value != disputed_value,
+            OracleError::InvalidDisputeValue
+        );
+        let record = ctx
+            .accounts
+            .price_history
+            .records
+            .iter_mut()
+            .find(|record| record.sequence == record_sequence)
+            .ok_or(OracleError::SubmissionNotFound)?;
+        require!(
+            record.dispute == DisputeOutcome::Undisputed,
+            OracleError::RecordAlreadyDisputed
+        );
+        record.dispute = DisputeOutcome::Pending;
+
+        submitter_state.open_disputes = submitter_state
+            .open_disputes
+            .checked_add(1)
+            .ok_or(OracleError::MathOverflow)?;
+
+        let bond = state.dispute_bond;
+        if bond > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    &ctx.accounts.disputer.key(),
+                    &ctx.accounts.dispute.key(),
+                    bond,
+                ),
+                &[
+                    ctx.accounts.disputer.to_account_info(),
+                    ctx.accounts.dispute.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        let voting_ends_at = now.saturating_add(state.dispute_voting_period as i64);
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.oracle = state.key();
+        dispute.disputer = ctx.accounts.disputer.key();
+        dispute.submitter = submitter_state.owner;
+        dispute.record_sequence = record_sequence;
+        dispute.record_timestamp = record_timestamp;
+        dispute.disputed_value = disputed_value;
+        dispute.proposed_value = proposed_value;
+        dispute.bond = bond;
+        dispute.snapshot_slot = Clock::get()?.slot;
+        dispute.support_votes = 0;
+        dispute.oppose_votes = 0;
+        dispute.voting_ends_at = voting_ends_at;
+        dispute.bump = ctx.bumps.dispute;
+
+        emit!(DisputeOpened {
+            oracle: state.key(),
+            disputer: dispute.disputer,
+            submitter: dispute.submitter,
+            record_sequence,
+            record_timestamp,
+            disputed_value,
+            proposed_value,
+            bond,
+            voting_ends_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn vote_dispute(ctx: Context<VoteDispute>, support: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let state = &ctx.accounts.oracle_state;
+        let user_state = &ctx.accounts.user_state;
+        let dispute = &mut ctx.accounts.dispute;
+
+        require!(now < dispute.voting_ends_at, OracleError::DisputeVotingClosed);
+        require!(
+            user_state.owner != dispute.disputer && user_state.owner != dispute.submitter,
+            OracleError::DisputePartyCannotVote
+        );
+        require!(
+            !state.is_blacklisted(&user_state.owner),
+            OracleError::AccountBlacklisted
+        );
+        let weight = user_state.weight_before(dispute.snapshot_slot);
+        require!(weight > 0, OracleError::NoStakeAtSnapshot);
+        require!(weight >= state.min_vote_stake, OracleError::StakeBelowVoteMinimum);
+
+        let tally = if support {
+            &mut dispute.support_votes
+        } else {
+            &mut dispute.oppose_votes
+        };
+        *tally = tally.checked_add(weight).ok_or(OracleError::MathOverflow)?;
+
+        let vote = &mut ctx.accounts.dispute_vote;
+        vote.voter = user_state.owner;
+        vote.support = support;
+        vote.weight = weight;
+        vote.bump = ctx.bumps.dispute_vote;
+
+        emit!(DisputeVoted {
+            dispute: dispute.key(),
+            voter: user_state.owner,
+            support,
+            weight,
+        });
+
+        Ok(())
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let state = &mut ctx.accounts.oracle_state;
+        let submitter_state = &mut ctx.accounts.submitter_state;
+        let dispute = &ctx.accounts.dispute;
+
+        require!(now >= dispute.voting_ends_at, OracleError::DisputeVotingOpen);
+
+        let upheld = dispute.support_votes > dispute.oppose_votes
+            && dispute.support_votes.saturating_add(dispute.oppose_votes)
+                > state.quorum_threshold(clock.slot);
+        submitter_state.open_disputes = submitter_state.open_disputes.saturating_sub(1);
+
+        let mut slashed = 0;
+        if upheld {
+            require!(
+                submitter_state.delegated_out == 0 || ctx.accounts.submitter_delegation.is_some(),
+                OracleError::SlashAccountsMissing
+            );
+            slashed = slash_stake(
+                state,
+                submitter_state,
+                ctx.accounts.submitter_delegation.as_mut(),
+                ctx.accounts.delegate_state.as_mut(),
+                now,
+            )?;
+            if slashed > 0 {
+                match state.stake_kind {
+                    StakeKind::Native => {
+                        let native_vault = ctx
+                            .accounts
+                            .native_vault
+                            .as_ref()
+                            .ok_or(OracleError::SlashAccountsMissing)?;
+                        transfer_from_native_vault(
+                            state,
+                            native_vault,
+                            &ctx.accounts.disputer.to_account_info(),
+                            &ctx.accounts.system_program,
+                            slashed,
+                        )?;
+                    }
+                    _ => {
+                        let (
+                            Some(weight_mint),
+                            Some(oracle_vault),
+                            Some(disputer_token_account),
+                            Some(token_program),
+                        ) = (
+                            ctx.accounts.weight_mint.as_ref(),
+                            ctx.accounts.oracle_vault.as_ref(),
+                            ctx.accounts.disputer_token_account.as_ref(),
+                            ctx.accounts.token_program.as_ref(),
+                        )
+                        else {
+                            return err!(OracleError::SlashAccountsMissing);
+                        };
+                        transfer_from_vault(
+                            state,
+                            weight_mint,
+                            oracle_vault,
+                            disputer_token_account,
+                            token_program,
+                            slashed,
+                        )?;
+                    }
+                }
+            }
+        } else {
+            // The bond joins the reward pool; the account's rent still goes
+            // back to the disputer on close.
+            let dispute_info = dispute.to_account_info();
+            let state_info = state.to_account_info();
+            **dispute_info.try_borrow_mut_lamports()? = dispute_info
+                .lamports()
+                .checked_sub(dispute.bond)
+                .ok_or(OracleError::MathUnderflow)?;
+            **state_info.try_borrow_mut_lamports()? = state_info
+                .lamports()
+                .checked_add(dispute.bond)
+                .ok_or(OracleError::MathOverflow)?;
+        }
+
+        let outcome = if upheld {
+            DisputeOutcome::Upheld
+        } else {
+            DisputeOutcome::Rejected
+        };
+        // The entry may have rotated out of the history while voting ran.
+        if let Some(record) = ctx.accounts.price_history.records.iter_mut().find(|record| {
+            record.sequence == dispute.record_sequence && record.dispute == DisputeOutcome::Pending
+        }) {
+            record.dispute = outcome;
+        }
+        state.last_timestamp = now;
+
+        emit!(DisputeResolved {
+            oracle: state.key(),
+            disputer: dispute.disputer,
+            submitter: dispute.submitter,
+            record_sequence: dispute.record_sequence,
+            record_timestamp: dispute.record_timestamp,
+            upheld,
+            slashed,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_receipt_mint(ctx: Context<CreateReceiptMint>) -> Result<()> {
+        let state = &mut ctx.accounts.oracle_state;
+
+        // Stake deposited before the mint existed has no receipts to burn.
+        require!(
+            state.receipt_mint == Pubkey::default() && state.total_deposited_tokens == 0,
+            OracleError::ReceiptMintUnavailable
+        );
+        // Slashing takes stake without burning the holder's receipts, which
+        // may already have moved on, so receipts and disputes are exclusive.
+        require!(
+            state.dispute_voting_period == 0,
+            OracleError::ReceiptsWithDisputes
+        );
+        state.receipt_mint = ctx.accounts.receipt_mint.key();
+
+        emit!(ReceiptMintCreated {
+            oracle: state.key(),
+            receipt_mint: state.receipt_mint,
+        });
+
+        Ok(())
+    }
+
+    pub fn freeze_receipts(ctx: Context<SetReceiptsFrozen>) -> Result<()> {
+        let owner = ctx.accounts.receipt_account.owner;
+        require!(
+            ctx.accounts.oracle_state.is_blacklisted(&owner),
+            OracleError::ReceiptOwnerNotBlacklisted
+        );
+
+        set_receipts_frozen(
+            &ctx.accounts.oracle_state,
+            &ctx.accounts.receipt_mint,
+            &ctx.accounts.receipt_account,
+            &ctx.accounts.token_program,
+            true,
+        )?;
+
+        emit!(ReceiptsFrozenChanged {
+            owner,
+            account: ctx.accounts.receipt_account.key(),
+            frozen: true,
+        });
+
+        Ok(())
+    }
+
+    pub fn thaw_receipts(ctx: Context<SetReceiptsFrozen>) -> Result<()> {
+        let owner = ctx.accounts.receipt_account.owner;
+        require!(
+            !ctx.accounts.oracle_state.is_blacklisted(&owner),
+            OracleError::ReceiptOwnerBlacklisted
+        );
+
+        set_receipts_frozen(
+            &ctx.accounts.oracle_state,
+            &ctx.accounts.receipt_mint,
+            &ctx.accounts.receipt_account,
+            &ctx.accounts.token_program,
+            false,
+        )?;
+
+        emit!(ReceiptsFrozenChanged {
+            owner,
+            account: ctx.accounts.receipt_account.key(),
+            frozen: false,
+        });
+
+        Ok(())
+    }
+}
+
+fn process_deposit(ctx: Context<DepositTokens>, amount: u64, lock_seconds: u64) -> Result<()> {
+    require!(amount > 0, OracleError::AmountMustBePositive);
+
+    let now = Clock::get()?.unix_timestamp;
+    let state = &mut ctx.accounts.oracle_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    let (expected_user, user_bump) = Pubkey::find_program_address(
+        &[
+            b"user",
+            state.key().as_ref(),
+            ctx.accounts.user.key().as_ref(),
+        ],
+        ctx.program_id,
+    );
+    require_keys_eq!(expected_user, user_state.key(), OracleError::InvalidPda);
+    user_state.ensure_initialized(state.key(), ctx.accounts.user.key(), user_bump)?;
+
+    unlock_tokens_if_possible(user_state, now);
+    apply_ve_lock(state, user_state, lock_seconds, now)?;
+
+    validate_weight_mint(&ctx.accounts.weight_mint.to_account_info())?;
+
+    // Credit what reached the vault, which is less than `amount` when the
+    // mint charges a transfer fee.
+    let vault_before = ctx.accounts.oracle_vault.amount;
+    let transfer_accounts = token_interface::TransferChecked {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        mint: ctx.accounts.weight_mint.to_account_info(),
+        to: ctx.accounts.oracle_vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts),
+        amount,
+        ctx.accounts.weight_mint.decimals,
+    )?;
+    ctx.accounts.oracle_vault.reload()?;
+    let amount = ctx
+        .accounts
+        .oracle_vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or(OracleError::MathUnderflow)?;
+    require!(amount > 0, OracleError::AmountMustBePositive);
+
+    record_deposit(state, user_state, amount, now)?;
+    mint_receipts(
+        state,
+        &ctx.accounts.receipt_mint,
+        &ctx.accounts.user_receipt_account,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(TokenDeposited {
+        user: ctx.accounts.user.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+fn init_price_history(history: &mut PriceHistory, oracle: Pubkey, bump: u8) {
+    history.oracle = oracle;
+    history.records = Vec::new();
+    history.next_sequence = 0;
+    history.bump = bump;
+}
+
+fn init_oracle_state(
+    state: &mut OracleState,
+    authority: Pubkey,
+    weight_mint: Pubkey,
+    stake_kind: StakeKind,
+    bump: u8,
+    params: InitializeOracleParams,
+) -> Result<()> {
+    require!(!params.name.is_empty(), OracleError::InvalidName);
+    require!(
+        params.name.len() <= OracleState::MAX_NAME_LEN,
+        OracleError::InvalidName
+    );
+    require!(
+        params.description.len() <= OracleState::MAX_DESCRIPTION_LEN,
+        OracleError::InvalidDescription
+    );
+    require!(params.reward_bps <= DENOMINATOR, OracleError::InvalidRewardRate);
+    require!(params.quorum_bps <= DENOMINATOR, OracleError::InvalidQuorum);
+    require!(
+        params.dispute_slash_bps <= DENOMINATOR,
+        OracleError::InvalidSlashRate
+    );
+    require!(params.decay_model.is_valid(), OracleError::InvalidDecayModel);
+    require!(
+        params.early_exit_penalty_bps <= DENOMINATOR,
+        OracleError::InvalidPenaltyRate
+    );
+    require!(
+        params.max_window_deviation_bps == 0 || params.deviation_window_seconds > 0,
+        OracleError::InvalidDeviationWindow
+    );
+    require!(
+        params.max_user_weight_bps <= DENOMINATOR,
+        OracleError::InvalidWeightCap
+    );
+    require!(
+        params.ve_max_boost_bps <= MAX_VE_BOOST_BPS,
+        OracleError::InvalidVeBoost
+    );
+    require!(
+        stake_kind != StakeKind::NftCollection || params.withdrawal_locking_period == 0,
+        OracleError::UnbondingUnsupported
+    );
+    require!(
+        params.proposal_voting_period == 0 || params.proposal_execution_grace > 0,
+        OracleError::InvalidExecutionGrace
+    );
+    require!(
+        params.dispute_voting_period == 0 || params.dispute_bond > 0,
+        OracleError::InvalidDisputeBond
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    state.authority = authority;
+    state.weight_mint = weight_mint;
+    state.stake_kind = stake_kind;
+    state.reward_bps = params.reward_bps;
+    state.half_life_seconds = params.half_life_seconds;
+    state.decay_model = params.decay_model;
+    state.heartbeat_seconds = params.heartbeat_seconds;
+    state.status = OracleStatus::Active;
+    state.max_update_deviation_bps = params.max_update_deviation_bps;
+    state.max_window_deviation_bps = params.max_window_deviation_bps;
+    state.deviation_window_seconds = params.deviation_window_seconds;
+    state.breaker_reset_time = now;
+    state.quorum = params.quorum;
+    state.quorum_bps = params.quorum_bps;
+    state.deposit_locking_period = params.deposit_locking_period;
+    state.withdrawal_locking_period = params.withdrawal_locking_period;
+    state.early_exit_penalty_bps = params.early_exit_penalty_bps;
+    state.ve_max_lock_seconds = params.ve_max_lock_seconds;
+    state.ve_max_boost_bps = params.ve_max_boost_bps;
+    state.max_user_weight = params.max_user_weight;
+    state.max_user_weight_bps = params.max_user_weight_bps;
+    state.weighting_mode = params.weighting_mode;
+    state.min_submit_stake = params.min_submit_stake;
+    state.min_vote_stake = params.min_vote_stake;
+    state.min_submission_interval = params.min_submission_interval;
+    state.access_mode = params.access_mode;
+    state.proposal_voting_period = params.proposal_voting_period;
+    state.proposal_execution_delay = params.proposal_execution_delay;
+    state.proposal_execution_grace = params.proposal_execution_grace;
+    state.appeal_bond = params.appeal_bond;
+    state.appeal_voting_period = params.appeal_voting_period;
+    state.min_blacklist_duration = params.min_blacklist_duration;
+    state.dispute_bond = params.dispute_bond;
+    state.dispute_voting_period = params.dispute_voting_period;
+    state.dispute_slash_bps = params.dispute_slash_bps;
+    state.treasury = params.treasury;
+    state.alpha = params.alpha;
+    state.weighted_sum = I256::default();
+    state.aggregated_value = 0;
+    state.latest_value = 0;
+    state.aggregated_weight = 0;
+    state.last_submission_time = now;
+    state.last_timestamp = now;
+    state.total_deposited_tokens = 0;
+    state.delegator_rewards_reserved = 0;
+    state.receipt_mint = Pubkey::default();
+    state.proposal_count = 0;
+    state.name = params.name;
+    state.description = params.description;
+    state.targets = Vec::new();
+    state.bump = bump;
+
+    Ok(())
+}
+
+fn apply_ve_lock(
+    state: &mut Account<OracleState>,
+    user_state: &mut Account<UserState>,
+    lock_seconds: u64,
+    now: i64,
+) -> Result<()> {
+    if lock_seconds > 0 {
+        require!(state.ve_max_lock_seconds > 0, OracleError::VeLockDisabled);
+        require!(
+            lock_seconds <= state.ve_max_lock_seconds,
+            OracleError::InvalidLockDuration
+        );
+        let lock_end = now.saturating_add(lock_seconds as i64);
+        user_state.ve_lock_end = user_state.ve_lock_end.max(lock_end);
+        sync_vote_weight(state, user_state, now)?;
+    }
+    Ok(())
+}
+
+/// Books a deposit that has reached the vault as a new locked lot.
+fn record_deposit(
+    state: &mut Account<OracleState>,
+    user_state: &mut Account<UserState>,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    let unlock_time = now.saturating_add(state.deposit_locking_period as i64);
+    user_state.add_deposit_lot(amount, unlock_time)?;
+    user_state.locked_tokens = user_state
+        .locked_tokens
+        .checked_add(amount)
+        .ok_or(OracleError::MathOverflow)?;
+
+    state.total_deposited_tokens = state
+        .total_deposited_tokens
+        .checked_add(amount)
+        .ok_or(OracleError::MathOverflow)?;
+
+    user_state.last_operation_timestamp = now;
+    sync_vote_weight(state, user_state, now)?;
+    state.last_timestamp = now;
+    Ok(())
+}
+
+fn process_native_deposit(
+    ctx: Context<DepositNative>,
+    amount: u64,
+    lock_seconds: u64,
+) -> Result<()> {
+    require!(amount > 0, OracleError::AmountMustBePositive);
+
+    let now = Clock::get()?.unix_timestamp;
+    let state = &mut ctx.accounts.oracle_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    user_state.ensure_initialized(state.key(), ctx.accounts.user.key(), ctx.bumps.user_state)?;
+    unlock_tokens_if_possible(user_state, now);
+    apply_ve_lock(state, user_state, lock_seconds, now)?;
+
+    invoke(
+        &system_instruction::transfer(
+            &ctx.accounts.user.key(),
+            &ctx.accounts.native_vault.key(),
+            amount,
+        ),
+        &[
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.native_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    record_deposit(state, user_state, amount, now)?;
+
+    emit!(TokenDeposited {
+        user: ctx.accounts.user.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Removes `amount` of unlocked stake for an immediate withdrawal. Oracles
+/// with a withdrawal locking period only release stake through the
+/// unbonding queue.
+fn debit_unlocked(
+    state: &mut Account<OracleState>,
+    user_state: &mut Account<UserState>,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    require!(
+        state.withdrawal_locking_period == 0,
+        OracleError::UnbondingRequired
+    );
+    unlock_tokens_if_possible(user_state, now);
+    require!(now >= user_state.ve_lock_end, OracleError::VeLockActive);
+    require!(user_state.open_disputes == 0, OracleError::StakeUnderDispute);
+    require!(
+        user_state.unlocked_tokens >= amount,
+        OracleError::InsufficientUnlockedTokens
+    );
+
+    user_state.unlocked_tokens = user_state
+        .unlocked_tokens
+        .checked_sub(amount)
+        .ok_or(OracleError::MathUnderflow)?;
+    state.total_deposited_tokens = state
+        .total_deposited_tokens
+        .checked_sub(amount)
+        .ok_or(OracleError::MathUnderflow)?;
+
+    sync_vote_weight(state, user_state, now)?;
+    Ok(())
+}
+
+/// Removes `dispute_slash_bps` of the submitter's own stake and returns the
+/// amount taken. Unlocked tokens go first, then locked lots, then queued
+/// withdrawals, newest first, then stake delegated away, so none of them is
+/// a way out of a dispute.
+fn slash_stake(
+    state: &mut Account<OracleState>,
+    user_state: &mut Account<UserState>,
+    delegation: Option<&mut Account<Delegation>>,
+    delegate_state: Option<&mut Account<UserState>>,
+    now: i64,
+) -> Result<u64> {
+    unlock_tokens_if_possible(user_state, now);
+    let queued = user_state
+        .pending_withdrawals
+        .iter()
+        .try_fold(0u64, |total, request| total.checked_add(request.amount))
+        .ok_or(OracleError::MathOverflow)?;
+    let own_stake = user_state
+        .unlocked_tokens
+        .checked_add(user_state.locked_tokens)
+        .and_then(|stake| stake.checked_add(queued))
+        .and_then(|stake| stake.checked_add(user_state.delegated_out))
+        .ok_or(OracleError::MathOverflow)?;
+    let amount = u64::try_from(
+        u128::from(own_stake) * u128::from(state.dispute_slash_bps) / u128::from(DENOMINATOR),
+    )
+    .map_err(|_| error!(OracleError::MathOverflow))?;
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let from_unlocked = amount.min(user_state.unlocked_tokens);
+    user_state.unlocked_tokens -= from_unlocked;
+    let mut remaining = amount - from_unlocked;
+    let from_locked = remaining.min(user_state.locked_tokens);
+    user_state.take_from_locked_lots(from_locked)?;
+    remaining -= from_locked;
+
+    // Queued withdrawals already left `total_deposited_tokens`.
+    while remaining > 0 {
+        let Some(request) = user_state.pending_withdrawals.last_mut() else {
+            break;
+        };
+        let take = remaining.min(request.amount);
+        request.amount -= take;
+        remaining -= take;
+        if request.amount == 0 {
+            user_state.pending_withdrawals.pop();
+        }
+    }
+    let from_queued = amount - from_unlocked - from_locked - remaining;
+
+    if remaining > 0 {
+        let delegation = delegation.ok_or(OracleError::SlashAccountsMissing)?;
+        if delegation.available_at == 0 {
+            let delegate_state = delegate_state.ok_or(OracleError::SlashAccountsMissing)?;
+            require!(
+                delegate_state.oracle == state.key() && delegate_state.owner == delegation.delegate,
+                OracleError::InvalidDelegate
+            );
+            // The slashed share forfeits its unclaimed reward to the pool.
+            let reward_index = delegate_state.delegation_reward_index;
+            let reward_before = delegation.pending_reward(reward_index)?;
+            delegation.amount = delegation
+                .amount
+                .checked_sub(remaining)
+                .ok_or(OracleError::MathUnderflow)?;
+            let forfeited = reward_before.saturating_sub(delegation.pending_reward(reward_index)?);
+            state.delegator_rewards_reserved =
+                state.delegator_rewards_reserved.saturating_sub(forfeited);
+            delegate_state.delegated_in = delegate_state
+                .delegated_in
+                .checked_sub(remaining)
+                .ok_or(OracleError::MathUnderflow)?;
+            sync_vote_weight(state, delegate_state, now)?;
+        } else {
+            delegation.amount = delegation
+                .amount
+                .checked_sub(remaining)
+                .ok_or(OracleError::MathUnderflow)?;
+        }
+        user_state.delegated_out = user_state
+            .delegated_out
+            .checked_sub(remaining)
+            .ok_or(OracleError::MathUnderflow)?;
+    }
+
+    state.total_deposited_tokens = state
+        .total_deposited_tokens
+        .checked_sub(amount - from_queued)
+        .ok_or(OracleError::MathUnderflow)?;
+
+    sync_vote_weight(state, user_state, now)?;
+    Ok(amount)
+}
+
+/// Removes `amount` of stake, drawing on locked lots if needed, and returns
+/// the early-exit penalty owed on it.
+fn debit_early(
+    state: &mut Account<OracleState>,
+    user_state: &mut Account<UserState>,
+    amount: u64,
+    now: i64,
+) -> Result<u64> {
+    require!(
+        state.early_exit_penalty_bps > 0,
+        OracleError::EarlyExitDisabled
+    );
+    unlock_tokens_if_possible(user_state, now);
+    require!(now >= user_state.ve_lock_end, OracleError::VeLockActive);
+    require!(user_state.open_disputes == 0, OracleError::StakeUnderDispute);
+    let available = user_state
+        .unlocked_tokens
+        .checked_add(user_state.locked_tokens)
+        .ok_or(OracleError::MathOverflow)?;
+    require!(available >= amount, OracleError::InsufficientDepositedTokens);
+
+    let from_unlocked = amount.min(user_state.unlocked_tokens);
+    user_state.unlocked_tokens -= from_unlocked;
+    user_state.take_from_locked_lots(amount - from_unlocked)?;
+    state.total_deposited_tokens = state
+        .total_deposited_tokens
+        .checked_sub(amount)
+        .ok_or(OracleError::MathUnderflow)?;
+
+    sync_vote_weight(state, user_state, now)?;
+
+    u64::try_from(
+        u128::from(amount) * u128::from(state.early_exit_penalty_bps)
+            / u128::from(DENOMINATOR),
+    )
+    .map_err(|_| error!(OracleError::MathOverflow))
+}
+
+/// Drops every pending withdrawal that has finished unbonding and returns
+/// their total. Queued tokens can still be slashed, so nothing leaves while
+/// a dispute is open.
+fn take_matured_withdrawals(user_state: &mut Account<UserState>, now: i64) -> Result<u64> {
+    require!(user_state.open_disputes == 0, OracleError::StakeUnderDispute);
+    let mut amount: u64 = 0;
+    for request in user_state.pending_withdrawals.iter() {
+        if now >= request.available_at {
+            amount = amount
+                .checked_add(request.amount)
+                .ok_or(OracleError::MathOverflow)?;
+        }
+    }
+    require!(amount > 0, OracleError::WithdrawalLocked);
+    user_state
+        .pending_withdrawals
+        .retain(|request| now < request.available_at);
+    Ok(amount)
+}
+
+fn process_vote(ctx: Context<Vote>, target: Pubkey, kind: VoteKind) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let state = &mut ctx.accounts.oracle_state;
+    let voter_state = &mut ctx.accounts.user_state;
+
+    let voter = voter_state.owner;
+    require!(
+        !state.is_blacklisted(&voter) && !state.is_blacklisted(&ctx.accounts.user.key()),
+        OracleError::AccountBlacklisted
+    );
+
+    sync_vote_weight(state, voter_state, now)?;
+    let idx = state.upsert_target(target, clock.slot)?;
+    let weight = voter_state.weight_before(state.targets[idx].snapshot_slot);
+    require!(weight > 0, OracleError::NoStakeAtSnapshot);
+    require!(weight >= state.min_vote_stake, OracleError::StakeBelowVoteMinimum);
+
+    match kind {
+        VoteKind::Blacklist => {
+            let round = state.targets[idx].pardon_round;
+            let vote = UserVote { target, weight, round };
+            // A vote voided by a pardon may be cast again.
+            match voter_state
+                .blacklist_votes
+                .iter()
+                .position(|cast| cast.target == target)
+            {
+                Some(existing) => {
+                    require!(
+                        voter_state.blacklist_votes[existing].round != round,
+                        OracleError::AlreadyVoted
+                    );
+                    voter_state.blacklist_votes[existing] = vote;
+                }
+                None => {
+                    require!(
+                        voter_state.blacklist_votes.len() < MAX_USER_VOTES,
+                        OracleError::TooManyVotes
+                    );
+                    voter_state.blacklist_votes.push(vote);
+                }
+            }
+            state.targets[idx].blacklist_votes = state.targets[idx]
+                .blacklist_votes
+                .checked_add(weight)
+                .ok_or(OracleError::MathOverflow)?;
+            let changed = state.refresh_blacklist_status(idx, now, clock.slot);
+            if changed {
+                emit!(BlacklistStatusChanged {
+                    target,
+                    is_blacklisted: state.targets[idx].is_blacklisted,
+                });
+            }
+        }
+        VoteKind::Whitelist => {
+            require!(
+                voter_state
+                    .whitelist_votes
+                    .iter()
+                    .all(|vote| vote.target != target),
+                OracleError::AlreadyVoted
+            );
+            require!(
+                voter_state.whitelist_votes.len() < MAX_USER_VOTES,
+                OracleError::TooManyVotes
+            );
+            state.targets[idx].whitelist_votes = state.targets[idx]
+                .whitelist_votes
+                .checked_add(weight)
+                .ok_or(OracleError::MathOverflow)?;
+            let changed = state.refresh_blacklist_status(idx, now, clock.slot);
+            let round = state.targets[idx].pardon_round;
+            voter_state.whitelist_votes.push(UserVote { target, weight, round });
+            if changed {
+                emit!(BlacklistStatusChanged {
+                    target,
+                    is_blacklisted: state.targets[idx].is_blacklisted,
+                });
+            }
+        }
+    }
+
+    voter_state.last_operation_timestamp = now;
+    state.last_timestamp = now;
+
+    emit!(Voted {
+        target,
+        voter,
+        is_blacklist: matches!(kind, VoteKind::Blacklist),
+        weight,
+    });
+
+    Ok(())
+}
+
+fn unlock_tokens_if_possible(user_state: &mut Account<UserState>, now: i64) {
+    let mut released: u64 = 0;
+    user_state.deposit_lots.retain(|lot| {
+        if now >= lot.unlock_time {
+            released = released.saturating_add(lot.amount);
+            false
+        } else {
+            true
+        }
+    });
+    if released == 0 {
+        return;
+    }
+    user_state.locked_tokens = user_state.locked_tokens.saturating_sub(released);
+    user_state.unlocked_tokens = user_state.unlocked_tokens.saturating_add(released);
+}
+
+/// Unlocked tokens plus a vote-escrow boost that shrinks linearly to zero
+/// as the lock runs out, plus any stake delegated in. Delegated stake is not
+/// boosted by the reporter's own lock.
+fn boosted_weight(state: &OracleState, user_state: &UserState, now: i64) -> Result<u64> {
+    let base = u128::from(user_state.unlocked_tokens);
+    let remaining = time_difference(user_state.ve_lock_end, now).min(state.ve_max_lock_seconds);
+    let boost = if remaining == 0 || state.ve_max_boost_bps == 0 {
+        0
+    } else {
+        base * u128::from(state.ve_max_boost_bps) * u128::from(remaining)
+            / (u128::from(DENOMINATOR) * u128::from(state.ve_max_lock_seconds))
+    };
+    u64::try_from(base + boost + u128::from(user_state.delegated_in))
+        .map_err(|_| error!(OracleError::MathOverflow))
+}
+
+/// Part of a reporter's reward owed to its delegators: the delegated share of
+/// its stake, less the reporter's commission.
+fn delegator_reward_share(user_state: &UserState, reward: u64) -> Result<u64> {
+    if user_state.delegated_in == 0 || reward == 0 {
+        return Ok(0);
+    }
+    let stake = u128::from(user_state.unlocked_tokens) + u128::from(user_state.delegated_in);
+    let delegated = u128::from(reward) * u128::from(user_state.delegated_in) / stake;
+    let commission = delegated * u128::from(user_state.commission_bps) / u128::from(DENOMINATOR);
+    u64::try_from(delegated - commission).map_err(|_| error!(OracleError::MathOverflow))
+}
+
+/// Pays a delegator everything accrued since their last payout and moves
+/// their snapshot up to `reward_index`.
+fn settle_delegation_reward<'info>(
+    state: &mut Account<'info, OracleState>,
+    delegation: &mut Account<'info, Delegation>,
+    reward_index: u128,
+    recipient: &AccountInfo<'info>,
+) -> Result<u64> {
+    let pending = delegation.pending_reward(reward_index)?;
+    delegation.reward_index = reward_index;
+    if pending > 0 {
+        state.delegator_rewards_reserved = state
+            .delegator_rewards_reserved
+            .checked_sub(pending)
+            .ok_or(OracleError::MathUnderflow)?;
+        pay_from_oracle(&state.to_account_info(), recipient, pending)?;
+    }
+    Ok(pending)
+}
+
+fn pay_from_oracle(state_info: &AccountInfo, recipient: &AccountInfo, amount: u64) -> Result<()> {
+    **state_info.try_borrow_mut_lamports()? = state_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(OracleError::MathUnderflow)?;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(OracleError::MathOverflow)?;
+    Ok(())
+}
+
+fn transfer_from_vault<'info>(
+    state: &Account<'info, OracleState>,
+    weight_mint: &InterfaceAccount<'info, Mint>,
+    oracle_vault: &InterfaceAccount<'info, TokenAccount>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let bump_seed = &[state.bump];
+    let signer_seeds: &[&[u8]] = &state.signer_seeds(bump_seed);
+    let transfer_accounts = token_interface::TransferChecked {
+        from: oracle_vault.to_account_info(),
+        mint: weight_mint.to_account_info(),
+        to: destination.to_account_info(),
+        authority: state.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            transfer_accounts,
+            &[signer_seeds],
+        ),
+        amount,
+        weight_mint.decimals,
+    )
+}
+
+/// Mints receipt tokens 1:1 for newly credited stake. A no-op on oracles
+/// without a receipt mint.
+fn mint_receipts<'info>(
+    state: &Account<'info, OracleState>,
+    receipt_mint: &Option<InterfaceAccount<'info, Mint>>,
+    receipt_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    if state.receipt_mint == Pubkey::default() {
+        return Ok(());
+    }
+    let (Some(receipt_mint), Some(receipt_account)) = (receipt_mint, receipt_account) else {
+        return err!(OracleError::ReceiptAccountsMissing);
+    };
+
+    let bump_seed = &[state.bump];
+    let signer_seeds: &[&[u8]] = &state.signer_seeds(bump_seed);
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: receipt_mint.to_account_info(),
+                to: receipt_account.to_account_info(),
+                authority: state.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        amount,
+    )
+}
+
+/// Burns the owner's receipt tokens for stake leaving the oracle. A no-op on
+/// oracles without a receipt mint.
+fn burn_receipts<'info>(
+    state: &Account<'info, OracleState>,
+    receipt_mint: &Option<InterfaceAccount<'info, Mint>>,
+    receipt_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    owner: &Signer<'info>,
+    token_program: Option<&Interface<'info, TokenInterface>>,
+    amount: u64,
+) -> Result<()> {
+    if state.receipt_mint == Pubkey::default() {
+        return Ok(());
+    }
+    let (Some(receipt_mint), Some(receipt_account), Some(token_program)) =
+        (receipt_mint, receipt_account, token_program)
+    else {
+        return err!(OracleError::ReceiptAccountsMissing);
+    };
+
+    // A blacklisted staker's receipts may be frozen; they can still leave,
+    // so thaw around the burn and freeze what remains.
+    let frozen = receipt_account.is_frozen();
+    if frozen {
+        set_receipts_frozen(state, receipt_mint, receipt_account, token_program, false)?;
+    }
+    token_interface::burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            token_interface::Burn {
+                mint: receipt_mint.to_account_info(),
+                from: receipt_account.to_account_info(),
+                authority: owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    if frozen {
+        set_receipts_frozen(state, receipt_mint, receipt_account, token_program, true)?;
+    }
+    Ok(())
+}
+
+/// Freezes or thaws a receipt account with the oracle's freeze authority.
+fn set_receipts_frozen<'info>(
+    state: &Account<'info, OracleState>,
+    receipt_mint: &InterfaceAccount<'info, Mint>,
+    receipt_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    frozen: bool,
+) -> Result<()> {
+    let bump_seed = &[state.bump];
+    let signer_seeds: &[&[u8]] = &state.signer_seeds(bump_seed);
+    if frozen {
+        token_interface::freeze_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_interface::FreezeAccount {
+                account: receipt_account.to_account_info(),
+                mint: receipt_mint.to_account_info(),
+                authority: state.to_account_info(),
+            },
+            &[signer_seeds],
+        ))
+    } else {
+        token_interface::thaw_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_interface::ThawAccount {
+                account: receipt_account.to_account_info(),
+                mint: receipt_mint.to_account_info(),
+                authority: state.to_account_info(),
+            },
+            &[signer_seeds],
+        ))
+    }
+}
+
+fn transfer_from_native_vault<'info>(
+    state: &Account<'info, OracleState>,
+    native_vault: &SystemAccount<'info>,
+    destination: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    let state_key = state.key();
+    let bump_seed = &[state.native_vault_bump];
+    let signer_seeds: &[&[u8]] = &[b"native_vault", state_key.as_ref(), bump_seed];
+    invoke_signed(
+        &system_instruction::transfer(&native_vault.key(), &destination.key(), amount),
+        &[
+            native_vault.to_account_info(),
+            destination.clone(),
+            system_program.to_account_info(),
+        ],
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
+
+/// Rejects Token-2022 weight mints whose extensions break staking: tokens
+/// that cannot enter the vault, a vault that would start frozen, a permanent
+/// delegate that could drain it, or any active transfer hook. Vault transfers
+/// pass no hook program or extra accounts, so every hooked transfer would
+/// fail. Extensions newer than the linked spl-token-2022, such as Pausable,
+/// fail to unpack and are rejected too.
+fn validate_weight_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    for extension in mint_state.get_extension_types()? {
+        match extension {
+            ExtensionType::NonTransferable | ExtensionType::PermanentDelegate => {
+                return err!(OracleError::UnsupportedMintExtension);
+            }
+            ExtensionType::DefaultAccountState => {
+                let default_state = mint_state.get_extension::<DefaultAccountState>()?;
+                require!(
+                    default_state.state != AccountState::Frozen as u8,
+                    OracleError::UnsupportedMintExtension
+                );
+            }
+            ExtensionType::TransferHook => {
+                let hook = mint_state.get_extension::<TransferHook>()?;
+                require!(
+                    Option::<Pubkey>::from(hook.program_id).is_none(),
+                    OracleError::UnsupportedMintExtension
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Brings the user's `vote_weight` up to date after a stake change: matured
+/// lots are released, the user's share of `total_vote_weight` is swapped and
+/// a checkpoint is recorded for snapshot votes. Votes already cast keep the
+/// weight they were tallied with.
+fn sync_vote_weight(
+    state: &mut Account<OracleState>,
+    user_state: &mut Account<UserState>,
+    now: i64,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    unlock_tokens_if_possible(user_state, now);
+    let boosted = boosted_weight(state, user_state, now)?;
+    let capped = boosted.min(state.weight_cap());
+    let weight = state.weighting_mode.apply(capped);
+    state.update_total_vote_weight(user_state.vote_weight, weight, slot)?;
+    if weight != user_state.vote_weight {
+        emit!(VoteWeightUpdated {
+            user: user_state.owner,
+            weight,
+            capped_weight: boosted - capped,
+        });
+    }
+    user_state.vote_weight = weight;
+    user_state.checkpoint_weight(slot);
+    Ok(())
+}
+
+/// Appends a record and returns the sequence number it was given.
+fn push_price_history(
+    history: &mut PriceHistory,
+    timestamp: i64,
+    aggregated_value: i128,
+    latest_value: i128,
+    weight: u64,
+) -> Result<u64> {
+    if history.records.len() == MAX_HISTORY_ENTRIES {
+        history.records.remove(0);
+    }
+    let sequence = history.next_sequence;
+    history.next_sequence = sequence.checked_add(1).ok_or(OracleError::MathOverflow)?;
+    history.records.push(PriceRecord {
+        sequence,
+        timestamp,
+        aggregated_value,
+        latest_value,
+        dispute: DisputeOutcome::Undisputed,
+        weight,
+        superseded: false,
+    });
+    Ok(sequence)
+}
+
+/// Whether `value` is more than `limit_bps / DENOMINATOR` away from
+/// `reference`. A zero reference has no meaningful relative band and never
+/// trips.
+fn exceeds_deviation(reference: i128, value: i128, limit_bps: u64) -> bool {
+    let base = reference.unsigned_abs();
+    if base == 0 {
+        return false;
+    }
+    let denominator = u128::from(DENOMINATOR);
+    let limit = u128::from(limit_bps);
+    let allowed = (base / denominator)
+        .checked_mul(limit)
+        .and_then(|whole| whole.checked_add((base % denominator) * limit / denominator))
+        .unwrap_or(u128::MAX);
+    reference.abs_diff(value) > allowed
+}
+
+fn time_difference(now: i64, previous: i64) -> u64 {
+    if now <= previous {
+        0
+    } else {
+        (now - previous) as u64
+    }
+}
+
+/// Floor of the square root, by Newton's method.
+fn integer_sqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// `round(2^(-i/64) * 2^64)` for `i` in `0..64`.
+const EXP2_NEG_TABLE_Q64: [u128; 64] = [
+    18_446_744_073_709_551_616, 18_248_035_989_933_441_397,
+    18_051_468_387_014_017_850, 17_857_018_207_719_734_201,
+    17_664_662_643_191_237_676, 17_474_379_130_265_907_514,
+    17_286_145_348_831_213_003, 17_099_939_219_206_581_124,
+    16_915_738_899_553_466_671, 16_733_522_783_313_321_064,
+    16_553_269_496_673_159_336, 16_374_957_896_058_427_985,
+    16_198_567_065_652_879_633, 16_024_076_314_945_163_556,
+    15_851_465_176_301_844_309, 15_680_713_402_566_563_766,
+    15_511_800_964_685_064_948, 15_344_708_049_355_799_071,
+    15_179_415_056_705_840_218, 15_015_902_597_991_835_029,
+    14_854_151_493_325_717_732, 14_694_142_769_424_923_741,
+    14_535_857_657_386_837_923, 14_379_277_590_487_216_477,
+    14_224_384_202_002_324_189, 14_071_159_323_054_531_591,
+    13_919_584_980_481_119_318, 13_769_643_394_726_039_683,
+    13_621_316_977_754_388_159, 13_474_588_330_989_340_155,
+    13_329_440_243_271_311_081, 13_185_855_688_839_100_305,
+    13_043_817_825_332_782_212, 12_903_309_991_818_110_089,
+    12_764_315_706_832_201_104, 12_626_818_666_450_273_141,
+    12_490_802_742_373_206_726, 12_356_251_980_035_707_703,
+    12_223_150_596_734_848_760, 12_091_482_979_778_770_282,
+    11_961_233_684_655_323_370, 11_832_387_433_220_440_222,
+    11_704_929_111_906_019_353, 11_578_843_769_947_115_458,
+    11_454_116_617_628_225_966, 11_330_733_024_548_468_561,
+    11_208_678_517_905_446_201, 11_087_938_780_797_598_309,
+    10_968_499_650_544_839_023, 10_850_347_117_027_285_504,
+    10_733_467_321_041_881_441, 10_617_846_552_676_722_988,
+    10_503_471_249_702_896_438, 10_390_327_995_983_639_004,
+    10_278_403_519_900_636_096, 10_167_684_692_797_270_502,
+    10_058_158_527_438_640_871, 9_949_812_176_488_168_858,
+    9_842_632_931_000_616_231, 9_736_608_218_931_335_184,
+    9_631_725_603_661_576_981, 9_527_972_782_539_685_958,
+    9_425_337_585_438_007_767, 9_323_807_973_325_342_579,
+];
+/// `round(ln(2) * 2^64)`.
+const LN2_Q64: u128 = 12_786_308_645_202_655_660;
+
+mod decay {
+    use super::*;
+
+    pub fn apply_decay(
+        value: u128,
+        elapsed: u64,
+        model: DecayModel,
+        half_life_seconds: u64,
+    ) -> Result<u128> {
+        if value == 0 {
+            return Ok(0);
+        }
+        let factor = factor(model, elapsed, half_life_seconds);
+        if factor >= WAD {
+            return Ok(value);
+        }
+        let decayed = value
+            .checked_mul(factor)
+            .ok_or(OracleError::MathOverflow)?
+            .checked_div(WAD)
+            .ok_or(OracleError::MathUnderflow)?;
+        Ok(decayed)
+    }
+
+    /// Share of a weight that survives `elapsed` seconds under `model`,
+    /// scaled by `WAD`.
+    pub fn factor(model: DecayModel, elapsed: u64, half_life_seconds: u64) -> u128 {
+        match model {
+            DecayModel::Exponential => decay_factor(elapsed, half_life_seconds),
+            DecayModel::Linear { window_seconds } => {
+                let remaining = window_seconds.saturating_sub(elapsed);
+                u128::from(remaining) * WAD / u128::from(window_seconds)
+            }
+            DecayModel::Step { cutoff_seconds } => {
+                if elapsed > cutoff_seconds {
+                    0
+                } else {
+                    WAD
+                }
+            }
+            DecayModel::None => WAD,
+        }
+    }
+
+    /// `2^(-elapsed / half_life_seconds)` scaled by `WAD`.
+    ///
+    /// The whole half-lives become a right shift. The fractional part `f` is
+    /// split as `f = i / 64 + r`: `2^(-i/64)` comes from `EXP2_NEG_TABLE_Q64`,
+    /// and `2^-r = e^(-r ln 2)` comes from a Taylor series with
+    /// `r ln 2 < 0.011`. All intermediate values are Q64 fixed point, so the
+    /// result is within 2 WAD units (`2e-18`) of the exact value, for any
+    /// elapsed time. It reaches 0 once `2^-x * WAD < 1`, which takes about 60
+    /// half-lives.
+    pub fn decay_factor(elapsed: u64, half_life_seconds: u64) -> u128 {
+        if elapsed == 0 || half_life_seconds == 0 {
+            return WAD;
+        }
+
+        let whole = elapsed / half_life_seconds;
+        if whole >= 64 {
+            return 0;
+        }
+        let rem = elapsed % half_life_seconds;
+        let frac = (u128::from(rem) << 64) / u128::from(half_life_seconds);
+
+        let factor_q64 = exp2_neg_frac_q64(frac);
+        ((factor_q64 * WAD) >> 64) >> whole
+    }
+
+    /// `2^-frac` in Q64 for `frac` in `[0, 1)` given in Q64.
+    fn exp2_neg_frac_q64(frac: u128) -> u128 {
+        const STEP_BITS: u32 = 58;
+        let index = (frac >> STEP_BITS) as usize;
+        let rest = frac & ((1 << STEP_BITS) - 1);
+
+        let y = (rest * LN2_Q64) >> 64;
+        let mut term = 1u128 << 64;
+        let mut sum = term;
+        for n in 1..=7u128 {
+            term = ((term * y) >> 64) / n;
+            if n % 2 == 1 {
+                sum -= term;
+            } else {
+                sum += term;
+            }
+        }
+
+        if index == 0 {
+            // The table entry is exactly 1.0, which would not fit the product.
+            return sum;
+        }
+        (EXP2_NEG_TABLE_Q64[index] * sum) >> 64
+    }
+
+    fn activity_factor(model: DecayModel, elapsed: u64, half_life_seconds: u64) -> u128 {
+        WAD.saturating_sub(factor(model, elapsed, half_life_seconds))
+    }
+
+    pub fn calculate_reward(
+        reward_pool: u128,
+        weight: u64,
+        elapsed: u64,
+        total_weight: u128,
+        alpha: u64,
+        model: DecayModel,
+        half_life_seconds: u64,
+    ) -> Result<u64> {
+        if reward_pool == 0 || weight == 0 || total_weight == 0 {
+            return Ok(0);
+        }
+        let activity = activity_factor(model, elapsed, half_life_seconds);
+        if activity == 0 {
+            return Ok(0);
+        }
+        let num = u128::from(weight)
+            .checked_mul(activity)
+            .ok_or(OracleError::MathOverflow)?
+            .checked_div(WAD)
+            .ok_or(OracleError::MathUnderflow)?;
+
+        let reward = u128::from(alpha)
+            .checked_mul(reward_pool)
+            .ok_or(OracleError::MathOverflow)?
+            .checked_mul(num)
+            .ok_or(OracleError::MathOverflow)?
+            .checked_div(total_weight)
+            .ok_or(OracleError::MathUnderflow)?;
+
+        u64::try_from(reward).map_err(|_| error!(OracleError::MathOverflow))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// xorshift64, so the property checks are reproducible without extra
+        /// dependencies.
+        struct Rng(u64);
+
+        impl Rng {
+            fn next(&mut self) -> u64 {
+                self.0 ^= self.0 << 13;
+                self.0 ^= self.0 >> 7;
+                self.0 ^= self.0 << 17;
+                self.0
+            }
+        }
+
+        fn reference(elapsed: u64, half_life_seconds: u64) -> f64 {
+            let exponent = elapsed as f64 / half_life_seconds as f64;
+            (-exponent).exp2() * WAD as f64
+        }
+
+        fn assert_close(elapsed: u64, half_life_seconds: u64) {
+            let got = decay_factor(elapsed, half_life_seconds) as f64;
+            let expected = reference(elapsed, half_life_seconds);
+            // f64 only carries ~16 significant digits, so allow for that
+            // on large values and for 2 WAD units on small ones.
+            let tolerance = (expected * 1e-14).max(2.0);
+            assert!(
+                (got - expected).abs() <= tolerance,
+                "elapsed {elapsed}, half-life {half_life_seconds}: got {got}, expected {expected}"
+            );
+        }
+
+        #[test]
+        fn integer_half_lives_are_exact_powers_of_two() {
+            for k in 0..60u64 {
+                assert_eq!(decay_factor(k * 3_600, 3_600), WAD >> k);
+            }
+            assert_eq!(decay_factor(64 * 3_600, 3_600), 0);
+        }
+
+        #[test]
+        fn matches_f64_reference_on_random_inputs() {
+            let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+            for _ in 0..20_000 {
+                let half_life = rng.next() % 1_000_000 + 1;
+                let elapsed = rng.next() % (half_life * 70);
+                assert_close(elapsed, half_life);
+            }
+        }
+
+        #[test]
+        fn matches_f64_reference_between_half_lives() {
+            // Points between whole half-lives, where a linear interpolation
+            // of the powers of two would be least accurate.
+            for k in 0..60u64 {
+                for step in 1..64u64 {
+                    assert_close(k * 6_400 + step * 100, 6_400);
+                }
+            }
+        }
+
+        #[test]
+        fn matches_f64_reference_at_extremes() {
+            assert_close(1, u64::MAX);
+            assert_close(u64::MAX - 1, u64::MAX);
+            assert_close(u64::MAX, 1);
+            assert_close(59, 1);
+        }
+
+        #[test]
+        fn alternative_models_follow_their_curves() {
+            let linear = DecayModel::Linear { window_seconds: 100 };
+            assert_eq!(factor(linear, 0, 0), WAD);
+            assert_eq!(factor(linear, 25, 0), WAD * 3 / 4);
+            assert_eq!(factor(linear, 100, 0), 0);
+            assert_eq!(factor(linear, u64::MAX, 0), 0);
+
+            let step = DecayModel::Step { cutoff_seconds: 60 };
+            assert_eq!(factor(step, 60, 0), WAD);
+            assert_eq!(factor(step, 61, 0), 0);
+
+            assert_eq!(factor(DecayModel::None, u64::MAX, 1), WAD);
+            assert_eq!(activity_factor(DecayModel::None, u64::MAX, 1), 0);
+            assert_eq!(activity_factor(step, 61, 0), WAD);
+        }
+
+        #[test]
+        fn is_monotonic_and_multiplicative() {
+            let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+            for _ in 0..5_000 {
+                let half_life = rng.next() % 100_000 + 1;
+                let a = rng.next() % (half_life * 4);
+                let b = rng.next() % (half_life * 4);
+                assert!(decay_factor(a + 1, half_life) <= decay_factor(a, half_life));
+
+                let combined = decay_factor(a + b, half_life);
+                let chained = decay_factor(a, half_life) * decay_factor(b, half_life) / WAD;
+                assert!(combined.abs_diff(chained) <= 4, "a {a}, b {b}, half-life {half_life}");
+            }
+        }
+    }
+}
+
+/// Weighted-sum accumulator behind `aggregated_value`.
+///
+/// The oracle keeps `S = sum(value_i * weight_i)` next to `Q = sum(weight_i)`
+/// and publishes `S / Q`, instead of feeding the truncated mean back into the
+/// next update. `S` is a signed 256-bit integer: a single contribution is at
+/// most `2^127 * 2^64 = 2^191` for any `i128` value and any `u64` stake, so the
+/// sum has 64 bits of headroom before it can overflow.
+///
+/// Error bounds: every decay step truncates `S` and `Q` by less than one unit
+/// each, and the published mean is truncated towards zero once. After `k`
+/// decay steps the mean therefore differs from the exact weighted mean `p` by
+/// less than `1 + k * (|p| + 1) / Q` value units. The error no longer compounds
+/// through the mean itself, and it shrinks as more weight is staked.
+mod accumulator {
+    use super::*;
+
+    const LOW_MASK: u128 = u64::MAX as u128;
+
+    impl I256 {
+        fn from_parts(negative: bool, hi: u128, lo: u128) -> Self {
+            Self {
+                negative: negative && (hi != 0 || lo != 0),
+                hi,
+                lo,
+            }
+        }
+
+        /// Exact `value * weight`.
+        pub fn product(value: i128, weight: u128) -> Self {
+            let (hi, lo) = mul_wide(value.unsigned_abs(), weight);
+            Self::from_parts(value < 0, hi, lo)
+        }
+
+        pub fn is_zero(&self) -> bool {
+            self.hi == 0 && self.lo == 0
+        }
+
+        pub fn checked_add(self, other: Self) -> Option<Self> {
+            if self.negative == other.negative {
+                let (lo, carry) = self.lo.overflowing_add(other.lo);
+                let hi = self
+                    .hi
+                    .checked_add(other.hi)?
+                    .checked_add(u128::from(carry))?;
+                return Some(Self::from_parts(self.negative, hi, lo));
+            }
+            let (larger, smaller) = if (self.hi, self.lo) >= (other.hi, other.lo) {
+                (self, other)
+            } else {
+                (other, self)
+            };
+            let (lo, borrow) = larger.lo.overflowing_sub(smaller.lo);
+            let hi = larger.hi - smaller.hi - u128::from(borrow);
+            Some(Self::from_parts(larger.negative, hi, lo))
+        }
+
+        pub fn checked_sub(self, other: Self) -> Option<Self> {
+            self.checked_add(Self::from_parts(!other.negative, other.hi, other.lo))
+        }
+
+        /// `self * factor / WAD`, truncated towards zero.
+        pub fn mul_wad(self, factor: u128) -> Option<Self> {
+            let (q_hi, q_lo, rem) = div_wide(self.hi, self.lo, WAD)?;
+            let (carry, lo) = mul_wide(q_lo, factor);
+            let hi = q_hi.checked_mul(factor)?.checked_add(carry)?;
+            let (lo, overflow) = lo.overflowing_add(rem.checked_mul(factor)? / WAD);
+            let hi = hi.checked_add(u128::from(overflow))?;
+            Some(Self::from_parts(self.negative, hi, lo))
+        }
+
+        /// `self / divisor`, truncated towards zero. `None` if the quotient
+        /// does not fit in an `i128`.
+        pub fn checked_div(self, divisor: u128) -> Option<i128> {
+            let (q_hi, q_lo, _) = div_wide(self.hi, self.lo, divisor)?;
+            if q_hi != 0 {
+                return None;
+            }
+            if self.negative {
+                0i128.checked_sub_unsigned(q_lo)
+            } else {
+                i128::try_from(q_lo).ok()
+            }
+        }
+    }
+
+    /// Full 256-bit product of two `u128`s as `(hi, lo)`.
+    fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+        let (a_hi, a_lo) = (a >> 64, a & LOW_MASK);
+        let (b_hi, b_lo) = (b >> 64, b & LOW_MASK);
+        let lo_lo = a_lo * b_lo;
+        let lo_hi = a_lo * b_hi;
+        let hi_lo = a_hi * b_lo;
+        let hi_hi = a_hi * b_hi;
+        let mid = (lo_lo >> 64) + (lo_hi & LOW_MASK) + (hi_lo & LOW_MASK);
+        let lo = (lo_lo & LOW_MASK) | (mid << 64);
+        let hi = hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (mid >> 64);
+        (hi, lo)
+    }
+
+    /// Divides the 256-bit value `(hi, lo)` by `divisor`, returning
+    /// `(quotient_hi, quotient_lo, remainder)`.
+    fn div_wide(hi: u128, lo: u128, divisor: u128) -> Option<(u128, u128, u128)> {
+        if divisor == 0 {
+            return None;
+        }
+        let q_hi = hi / divisor;
+        let mut rem = hi % divisor;
+        if rem == 0 {
+            return Some((q_hi, lo / divisor, lo % divisor));
+        }
+        if divisor <= LOW_MASK {
+            let upper = (rem << 64) | (lo >> 64);
+            let (q1, r1) = (upper / divisor, upper % divisor);
+            let lower = (r1 << 64) | (lo & LOW_MASK);
+            let (q0, r0) = (lower / divisor, lower % divisor);
+            return Some((q_hi, (q1 << 64) | q0, r0));
+        }
+        let mut q_lo = 0u128;
+        for bit in (0..128).rev() {
+            let carry = rem >> 127;
+            rem = (rem << 1) | ((lo >> bit) & 1);
+            q_lo <<= 1;
+            if carry == 1 || rem >= divisor {
+                rem = rem.wrapping_sub(divisor);
+                q_lo |= 1;
+            }
+        }
+        Some((q_hi, q_lo, rem))
+    }
+
+    /// Applies a WAD-scaled decay factor to the weighted sum.
+    pub fn decay(sum: I256, factor: u128) -> Result<I256> {
+        if factor >= WAD {
+            return Ok(sum);
+        }
+        sum.mul_wad(factor)
+            .ok_or_else(|| error!(OracleError::MathOverflow))
+    }
+
+    /// Sums `(value, weight, age)` contributions that each fade by their own
+    /// age under `model`. Returns the weighted sum and the total weight.
+    pub fn windowed(
+        contributions: impl Iterator<Item = (i128, u64, u64)>,
+        model: DecayModel,
+        half_life_seconds: u64,
+    ) -> Result<(I256, u128)> {
+        let mut sum = I256::default();
+        let mut total = 0u128;
+        for (value, weight, age) in contributions {
+            let weight = decay::apply_decay(u128::from(weight), age, model, half_life_seconds)?;
+            if weight == 0 {
+                continue;
+            }
+            sum = sum
+                .checked_add(I256::product(value, weight))
+                .ok_or(OracleError::MathOverflow)?;
+            total = total.checked_add(weight).ok_or(OracleError::MathOverflow)?;
+        }
+        Ok((sum, total))
+    }
+
+    /// Replaces a submitter's previous contribution with `(value, weight)`.
+    ///
+    /// `sum` and `total_weight` must already be decayed to the current time,
+    /// as must `previous_weight`. Because `total_weight` and `previous_weight`
+    /// are truncated independently, the removed weight is capped at
+    /// `total_weight`. Returns the new sum, the new total weight and the mean.
+    pub fn submit(
+        sum: I256,
+        total_weight: u128,
+        previous_value: i128,
+        previous_weight: u128,
+        value: i128,
+        weight: u128,
+    ) -> Result<(I256, u128, i128)> {
+        let removed_weight = previous_weight.min(total_weight);
+        let new_weight = total_weight
+            .checked_sub(removed_weight)
+            .ok_or(OracleError::MathUnderflow)?
+            .checked_add(weight)
+            .ok_or(OracleError::MathOverflow)?;
+        require!(new_weight > 0, OracleError::ZeroWeightAfterUpdate);
+
+        let remaining = if removed_weight == total_weight {
+            I256::default()
+        } else {
+            sum.checked_sub(I256::product(previous_value, removed_weight))
+                .ok_or(OracleError::MathOverflow)?
+        };
+        let new_sum = remaining
+            .checked_add(I256::product(value, weight))
+            .ok_or(OracleError::MathOverflow)?;
+        let mean = new_sum
+            .checked_div(new_weight)
+            .ok_or(OracleError::MathOverflow)?;
+
+        Ok((new_sum, new_weight, mean))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const SUPPLY: u128 = u64::MAX as u128;
+
+        fn half_life_factor() -> u128 {
+            WAD / 2
+        }
+
+        #[test]
+        fn wide_product_matches_u128_when_small() {
+            let product = I256::product(-123_456_789, 987_654_321);
+            assert!(product.negative);
+            assert_eq!(product.hi, 0);
+            assert_eq!(product.lo, 123_456_789 * 987_654_321);
+        }
+
+        #[test]
+        fn extreme_values_with_full_supply_do_not_overflow() {
+            for value in [i128::MAX, i128::MIN, i128::MAX / 3, -1, 1] {
+                let (sum, weight, mean) =
+                    submit(I256::default(), 0, 0, 0, value, SUPPLY).unwrap();
+                assert_eq!(weight, SUPPLY);
+                assert_eq!(mean, value);
+
+                let decayed = decay(sum, half_life_factor()).unwrap();
+                let (_, _, mean) =
+                    submit(decayed, weight / 2, value, SUPPLY / 2, value, SUPPLY).unwrap();
+                assert_eq!(mean, value);
+            }
+        }
+
+        #[test]
+        fn opposite_extremes_average_out() {
+            let (sum, weight, _) =
+                submit(I256::default(), 0, 0, 0, i128::MAX, SUPPLY).unwrap();
+            let (_, weight, mean) = submit(sum, weight, 0, 0, -i128::MAX, SUPPLY).unwrap();
+            assert_eq!(weight, 2 * SUPPLY);
+            assert_eq!(mean, 0);
+        }
+
+        #[test]
+        fn many_full_supply_submitters_stay_in_range() {
+            let mut sum = I256::default();
+            let mut weight = 0u128;
+            for round in 0..1_000 {
+                sum = decay(sum, WAD - WAD / 1_000).unwrap();
+                weight -= weight / 1_000;
+                let value = if round % 2 == 0 { i128::MAX } else { i128::MIN + 1 };
+                let (next_sum, next_weight, mean) =
+                    submit(sum, weight, 0, 0, value, SUPPLY).unwrap();
+                assert!(mean > i128::MIN);
+                sum = next_sum;
+                weight = next_weight;
+            }
+        }
+
+        #[test]
+        fn repeated_updates_do_not_drift() {
+            // Two reporters alternate without decay. Truncating the mean and
+            // multiplying it back used to walk away from the true average.
+            let mut sum = I256::default();
+            let mut weight = 0u128;
+            let mut last = [(0i128, 0u128); 2];
+            for round in 0..10_000usize {
+                let reporter = round % 2;
+                let value = if reporter == 0 { 10 } else { 13 };
+                let stake = if reporter == 0 { 3 } else { 7 };
+                let (prev_value, prev_weight) = last[reporter];
+                let (next_sum, next_weight, mean) =
+                    submit(sum, weight, prev_value, prev_weight, value, stake).unwrap();
+                sum = next_sum;
+                weight = next_weight;
+                last[reporter] = (value, stake);
+                if round > 0 {
+                    // exact mean is (10 * 3 + 13 * 7) / 10 = 12.1
+                    assert_eq!(mean, 12);
+                }
+            }
+            assert_eq!(sum, I256::product(121, 1));
+        }
+
+        #[test]
+        fn decay_stays_within_one_unit_of_exact() {
+            let value = I256::product(i128::MAX, SUPPLY);
+            let decayed = decay(value, half_life_factor()).unwrap();
+            let twice = decayed.checked_add(decayed).unwrap();
+            let diff = value.checked_sub(twice).unwrap();
+            assert!(!diff.negative);
+            assert_eq!(diff.hi, 0);
+            assert!(diff.lo <= 2);
+        }
+
+        #[test]
+        fn windowed_contributions_expire_by_their_own_age() {
+            let linear = DecayModel::Linear { window_seconds: 100 };
+            let (sum, total) =
+                windowed([(10, 100, 50), (20, 100, 150)].into_iter(), linear, 0).unwrap();
+            assert_eq!(total, 50);
+            assert_eq!(sum.checked_div(total), Some(10));
+
+            // A steady stream of newer submissions does not keep an old one
+            // alive past the cutoff.
+            let step = DecayModel::Step { cutoff_seconds: 60 };
+            let (sum, total) =
+                windowed([(7, 5, 61), (3, 5, 30), (3, 5, 0)].into_iter(), step, 0).unwrap();
+            assert_eq!(total, 10);
+            assert_eq!(sum.checked_div(total), Some(3));
+        }
+
+        #[test]
+        fn division_handles_large_divisors() {
+            let divisor = u128::MAX / 3;
+            let value = I256::product(-1_000_000, divisor);
+            assert_eq!(value.checked_div(divisor), Some(-1_000_000));
+            assert_eq!(I256::product(i128::MIN, 1).checked_div(1), Some(i128::MIN));
+            assert_eq!(I256::product(i128::MAX, 4).checked_div(2), None);
+        }
+    }
+}
+
+/// Per-user vote weight history for snapshot voting.
+///
+/// Governance decisions weigh votes by the weight a staker held strictly
+/// before the slot they opened in, so tokens borrowed or deposited to swing a
+/// live decision carry no weight. When the history is full the two oldest
+/// entries merge into one holding the smaller weight, so old snapshots still
+/// resolve, to a lower bound of what was held.
+mod checkpoints {
+    use super::*;
+
+    pub fn record(checkpoints: &mut Vec<StakeCheckpoint>, slot: u64, weight: u64) {
+        if let Some(last) = checkpoints.last_mut() {
+            if last.slot == slot {
+                last.weight = weight;
+                return;
+            }
+            if last.weight == weight {
+                return;
+            }
+        }
+        if checkpoints.len() == MAX_STAKE_CHECKPOINTS {
+            let merged = checkpoints.remove(1);
+            checkpoints[0].weight = checkpoints[0].weight.min(merged.weight);
+        }
+        checkpoints.push(StakeCheckpoint { slot, weight });
+    }
+
+    pub fn weight_before(checkpoints: &[StakeCheckpoint], slot: u64) -> u64 {
+        checkpoints
+            .iter()
+            .rev()
+            .find(|checkpoint| checkpoint.slot < slot)
+            .map(|checkpoint| checkpoint.weight)
+            .unwrap_or(0)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn reads_weight_held_before_snapshot() {
+            let mut history = Vec::new();
+            record(&mut history, 10, 100);
+            record(&mut history, 20, 400);
+            record(&mut history, 20, 250);
+            record(&mut history, 30, 250);
+            assert_eq!(history.len(), 2);
+            assert_eq!(weight_before(&history, 10), 0);
+            assert_eq!(weight_before(&history, 11), 100);
+            assert_eq!(weight_before(&history, 20), 100);
+            assert_eq!(weight_before(&history, 21), 250);
+            assert_eq!(weight_before(&history, u64::MAX), 250);
+        }
+
+        #[test]
+        fn merges_oldest_when_full() {
+            let mut history = Vec::new();
+            for slot in 1..=MAX_STAKE_CHECKPOINTS as u64 + 1 {
+                let weight = if slot == 2 { 5 } else { slot * 10 };
+                record(&mut history, slot, weight);
+            }
+            assert_eq!(history.len(), MAX_STAKE_CHECKPOINTS);
+            assert_eq!(weight_before(&history, 1), 0);
+            assert_eq!(weight_before(&history, 2), 5);
+            assert_eq!(weight_before(&history, 3), 5);
+            assert_eq!(weight_before(&history, 4), 30);
+            assert_eq!(
+                weight_before(&history, u64::MAX),
+                (MAX_STAKE_CHECKPOINTS as u64 + 1) * 10
+            );
+        }
+    }
+}
+
+/// Reads just enough of a Metaplex token-metadata account to check
+/// collection membership, without depending on the metadata crate.
+mod metadata {
+    use super::*;
+
+    pub const PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+    const KEY_METADATA_V1: u8 = 4;
+    const CREATOR_SIZE: usize = 32 + 1 + 1;
+
+    struct Reader<'a> {
+        data: &'a [u8],
+        offset: usize,
+    }
+
+    impl<'a> Reader<'a> {
+        fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+            let bytes = self.data.get(self.offset..self.offset.checked_add(len)?)?;
+            self.offset += len;
+            Some(bytes)
+        }
+
+        fn u8(&mut self) -> Option<u8> {
+            self.take(1).map(|bytes| bytes[0])
+        }
+
+        fn u32(&mut self) -> Option<u32> {
+            self.take(4)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        }
+
+        fn pubkey(&mut self) -> Option<Pubkey> {
+            self.take(32)
+                .map(|bytes| Pubkey::new_from_array(bytes.try_into().unwrap()))
+        }
+
+        fn skip_string(&mut self) -> Option<()> {
+            let len = self.u32()? as usize;
+            self.take(len).map(|_| ())
+        }
+
+        /// Skips a Borsh `Option` whose payload is `len` bytes.
+        fn skip_option(&mut self, len: usize) -> Option<()> {
+            if self.u8()? == 1 {
+                self.take(len)?;
+            }
+            Some(())
+        }
+    }
+
+    /// Returns the collection of `mint` when its metadata marks it verified.
+    pub fn verified_collection(data: &[u8], mint: &Pubkey) -> Option<Pubkey> {
+        let mut reader = Reader { data, offset: 0 };
+        if reader.u8()? != KEY_METADATA_V1 {
+            return None;
+        }
+        reader.pubkey()?; // update_authority
+        if reader.pubkey()? != *mint {
+            return None;
+        }
+        reader.skip_string()?; // name
+        reader.skip_string()?; // symbol
+        reader.skip_string()?; // uri
+        reader.take(2)?; // seller_fee_basis_points
+        if reader.u8()? == 1 {
+            let creators = reader.u32()? as usize;
+            reader.take(creators.checked_mul(CREATOR_SIZE)?)?;
+        }
+        reader.take(2)?; // primary_sale_happened, is_mutable
+        reader.skip_option(1)?; // edition_nonce
+        reader.skip_option(1)?; // token_standard
+        if reader.u8()? != 1 {
+            return None;
+        }
+        let verified = reader.u8()? == 1;
+        let collection = reader.pubkey()?;
+        verified.then_some(collection)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn encode(mint: &Pubkey, creators: u32, collection: Option<(bool, Pubkey)>) -> Vec<u8> {
+            let mut data = vec![KEY_METADATA_V1];
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+            data.extend_from_slice(mint.as_ref());
+            for field in ["Orb #1", "ORB", "https://example.com/1.json"] {
+                data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+                data.extend_from_slice(field.as_bytes());
+            }
+            data.extend_from_slice(&500u16.to_le_bytes());
+            if creators > 0 {
+                data.push(1);
+                data.extend_from_slice(&creators.to_le_bytes());
+                for _ in 0..creators {
+                    data.extend_from_slice(Pubkey::new_unique().as_ref());
+                    data.extend_from_slice(&[1, 100 / creators as u8]);
+                }
+            } else {
+                data.push(0);
+            }
+            data.extend_from_slice(&[1, 1]);
+            data.extend_from_slice(&[1, 255]);
+            data.extend_from_slice(&[1, 0]);
+            match collection {
+                Some((verified, key)) => {
+                    data.extend_from_slice(&[1, verified as u8]);
+                    data.extend_from_slice(key.as_ref());
+                }
+                None => data.push(0),
+            }
+            // Trailing fields and zero padding are ignored.
+            data.extend_from_slice(&[0; 64]);
+            data
+        }
+
+        #[test]
+        fn reads_verified_collection() {
+            let mint = Pubkey::new_unique();
+            let collection = Pubkey::new_unique();
+            for creators in [0, 1, 3] {
+                let data = encode(&mint, creators, Some((true, collection)));
+                assert_eq!(verified_collection(&data, &mint), Some(collection));
+            }
+        }
+
+        #[test]
+        fn rejects_unverified_missing_or_foreign() {
+            let mint = Pubkey::new_unique();
+            let collection = Pubkey::new_unique();
+            let unverified = encode(&mint, 1, Some((false, collection)));
+            assert_eq!(verified_collection(&unverified, &mint), None);
+            let missing = encode(&mint, 1, None);
+            assert_eq!(verified_collection(&missing, &mint), None);
+            let verified = encode(&mint, 1, Some((true, collection)));
+            assert_eq!(verified_collection(&verified, &Pubkey::new_unique()), None);
+            assert_eq!(verified_collection(&verified[..100], &mint), None);
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeOracle<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    pub weight_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OracleState::SPACE,
+        seeds = [b"oracle", authority.key().as_ref(), weight_mint.key().as_ref()],
+        bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceHistory::SPACE,
+        seeds = [b"history", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = weight_mint,
+        associated_token::authority = oracle_state,
+        associated_token::token_program = token_program,
+    )]
+    pub oracle_vault: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeNativeOracle<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OracleState::SPACE,
+        seeds = [b"native_oracle", authority.key().as_ref()],
+        bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceHistory::SPACE,
+        seeds = [b"history", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+    /// Holds staked lamports; seeded apart from the oracle so stake never
+    /// mixes with the reward pool.
+    #[account(
+        mut,
+        seeds = [b"native_vault", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub native_vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeNftOracle<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OracleState::SPACE,
+        seeds = [b"oracle", authority.key().as_ref(), collection_mint.key().as_ref()],
+        bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceHistory::SPACE,
+        seeds = [b"history", oracle_state.key().as_ref()],
+        bump
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundOracle<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub weight_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.weight_mint == weight_mint.key(),
+        constraint = oracle_state.stake_kind == StakeKind::Token @ OracleError::NotTokenOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        associated_token::mint = weight_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = weight_mint,
+        associated_token::authority = oracle_state,
+        associated_token::token_program = token_program,
+    )]
+    pub oracle_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserState::SPACE,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == Pubkey::default() || user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    /// Required once the oracle has a receipt mint.
+    #[account(mut, address = oracle_state.receipt_mint @ OracleError::InvalidReceiptMint)]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::authority = user)]
+    pub user_receipt_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub weight_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.weight_mint == weight_mint.key(),
+        constraint = oracle_state.stake_kind == StakeKind::Token @ OracleError::NotTokenOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        associated_token::mint = weight_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = weight_mint,
+        associated_token::authority = oracle_state,
+        associated_token::token_program = token_program,
+    )]
+    pub oracle_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    /// Required once the oracle has a receipt mint.
+    #[account(mut, address = oracle_state.receipt_mint @ OracleError::InvalidReceiptMint)]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::authority = user)]
+    pub user_receipt_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawEarly<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub weight_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.weight_mint == weight_mint.key(),
+        constraint = oracle_state.stake_kind == StakeKind::Token @ OracleError::NotTokenOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        associated_token::mint = weight_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = weight_mint,
+        associated_token::authority = oracle_state,
+        associated_token::token_program = token_program,
+    )]
+    pub oracle_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = oracle_state.treasury @ OracleError::InvalidTreasury,
+        token::mint = weight_mint,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    /// Required once the oracle has a receipt mint.
+    #[account(mut, address = oracle_state.receipt_mint @ OracleError::InvalidReceiptMint)]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::authority = user)]
+    pub user_receipt_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct DepositNft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.stake_kind == StakeKind::NftCollection @ OracleError::NotNftOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Metaplex metadata PDA of `nft_mint`; parsed in `metadata`.
+    #[account(
+        seeds = [b"metadata", metadata::PROGRAM_ID.as_ref(), nft_mint.key().as_ref()],
+        seeds::program = metadata::PROGRAM_ID,
+        bump,
+        owner = metadata::PROGRAM_ID
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_nft_account: InterfaceAccount<'info, TokenAccount>,
+    /// Escrow seeded by depositor and mint, so only the depositor can
+    /// withdraw the NFT.
+    #[account(
+        init,
+        payer = user,
+        seeds = [
+            b"nft_escrow",
+            oracle_state.key().as_ref(),
+            user.key().as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump,
+        token::mint = nft_mint,
+        token::authority = oracle_state,
+        token::token_program = token_program,
+    )]
+    pub nft_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + NftLock::SPACE,
+        seeds = [b"nft_lock", nft_escrow.key().as_ref()],
+        bump
+    )]
+    pub nft_lock: Account<'info, NftLock>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserState::SPACE,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == Pubkey::default() || user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawNft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.stake_kind == StakeKind::NftCollection @ OracleError::NotNftOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_nft_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"nft_escrow",
+            oracle_state.key().as_ref(),
+            user.key().as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub nft_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"nft_lock", nft_escrow.key().as_ref()],
+        bump = nft_lock.bump,
+    )]
+    pub nft_lock: Account<'info, NftLock>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct DepositNative<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.is_native() @ OracleError::NotNativeOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"native_vault", oracle_state.key().as_ref()],
+        bump = oracle_state.native_vault_bump
+    )]
+    pub native_vault: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserState::SPACE,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == Pubkey::default() || user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawNative<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.is_native() @ OracleError::NotNativeOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"native_vault", oracle_state.key().as_ref()],
+        bump = oracle_state.native_vault_bump
+    )]
+    pub native_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawEarlyNative<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.is_native() @ OracleError::NotNativeOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"native_vault", oracle_state.key().as_ref()],
+        bump = oracle_state.native_vault_bump
+    )]
+    pub native_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        address = oracle_state.treasury @ OracleError::InvalidTreasury,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.stake_kind != StakeKind::NftCollection @ OracleError::NotTokenOracle
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    /// Required once the oracle has a receipt mint.
+    #[account(mut, address = oracle_state.receipt_mint @ OracleError::InvalidReceiptMint)]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, token::authority = user)]
+    pub user_receipt_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitValue<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    /// The staker's account; the signer is its owner or designated submitter.
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user_state.owner.as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.can_submit(&user.key()) @ OracleError::UnauthorizedSubmitter
+    )]
+    pub user_state: Account<'info, UserState>,
+    /// Receives the activity reward, so a hot key can submit without earning.
+    #[account(mut, address = user_state.owner)]
+    pub owner: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"history", oracle_state.key().as_ref()],
+        bump = price_history.bump,
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+    /// Required on permissioned oracles.
+    #[account(
+        seeds = [b"submitter", oracle_state.key().as_ref(), user_state.owner.as_ref()],
+        bump = submitter_permit.bump,
+    )]
+    pub submitter_permit: Option<Account<'info, SubmitterPermit>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateUserVoteWeights<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user_state.owner.as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user_state.owner.as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.can_submit(&user.key()) @ OracleError::UnauthorizedSubmitter
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateUserSettings<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+#[derive(Accounts)]
+#[instruction(to: Pubkey)]
+pub struct Delegate<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), to.as_ref()],
+        bump = delegate_state.bump,
+        constraint = delegate_state.owner == to @ OracleError::InvalidDelegate
+    )]
+    pub delegate_state: Account<'info, UserState>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Delegation::SPACE,
+        seeds = [b"delegation", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Undelegate<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), delegation.delegate.as_ref()],
+        bump = delegate_state.bump,
+    )]
+    pub delegate_state: Account<'info, UserState>,
+    #[account(
+        mut,
+        seeds = [b"delegation", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = delegation.bump,
+    )]
+    pub delegation: Account<'info, Delegation>,
 }
 
-fn to_i128(value: u128) -> Result<i128> {
-    i128::try_from(value).map_err(|_| error!(OracleError::MathOverflow))
-}
-
-fn process_pow2_neg_int(k: u64) -> u128 {
-    match k {
-        0 => 1_000_000_000_000_000_000,
-        1 => 500_000_000_000_000_000,
-        2 => 250_000_000_000_000_000,
-        3 => 125_000_000_000_000_000,
-        4 => 62_500_000_000_000_000,
-        5 => 31_250_000_000_000_000,
-        6 => 15_625_000_000_000_000,
-        7 => 7_812_500_000_000_000,
-        8 => 3_906_250_000_000_000,
-        9 => 1_953_125_000_000_000,
-        10 => 976_562_500_000_000,
-        11 => 488_281_250_000_000,
-        12 => 244_140_625_000_000,
-        13 => 122_070_312_500_000,
-        14 => 61_035_156_250_000,
-        15 => 30_517_578_125_000,
-        16 => 15_258_789_062_500,
-        17 => 7_629_394_531_250,
-        18 => 3_814_697_265_625,
-        19 => 1_907_348_632_812,
-        20 => 953_674_316_406,
-        21 => 476_837_158_203,
-        22 => 238_418_579_102,
-        23 => 119_209_289_551,
-        24 => 59_604_644_775,
-        25 => 29_802_322_388,
-        26 => 14_901_161_194,
-        27 => 7_450_580_597,
-        28 => 3_725_290_298,
-        29 => 1_862_645_149,
-        30 => 931_322_574,
-        31 => 465_661_287,
-        32 => 232_830_643,
-        33 => 116_415_322,
-        34 => 58_207_661,
-        35 => 29_103_831,
-        36 => 14_551_915,
-        37 => 7_275_958,
-        38 => 3_637_979,
-        39 => 1_818_989,
-        40 => 909_495,
-        41 => 454_747,
-        42 => 227_373,
-        43 => 113_687,
-        44 => 56_843,
-        45 => 28_422,
-        46 => 14_211,
-        47 => 7_105,
-        48 => 3_553,
-        49 => 1_776,
-        50 => 888,
-        51 => 444,
-        52 => 222,
-        53 => 111,
-        54 => 56,
-        55 => 28,
-        56 => 14,
-        57 => 7,
-        58 => 3,
-        59 => 2,
-        60 => 1,
-        _ => 0,
-    }
+#[derive(Accounts)]
+pub struct CompleteUndelegation<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"delegation", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump = delegation.bump,
+    )]
+    pub delegation: Account<'info, Delegation>,
 }
 
-mod decay {
-    use super::*;
-
-    pub fn apply_decay(value: u128, elapsed: u64, half_life_seconds: u64) -> Result<u128> {
-        if value == 0 {
-            return Ok(0);
-        }
-        if half_life_seconds == 0 || elapsed == 0 {
-            return Ok(value);
-        }
-        let factor = decay_factor(elapsed, half_life_seconds);
-        let decayed = value
-            .checked_mul(factor)
-            .ok_or(OracleError::MathOverflow)?
-            .checked_div(WAD)
-            .ok_or(OracleError::MathUnderflow)?;
-        Ok(decayed)
-    }
-
-    pub fn decay_factor(elapsed: u64, half_life_seconds: u64) -> u128 {
-        if elapsed == 0 || half_life_seconds == 0 {
-            return WAD;
-        }
-
-        let scaled = (u128::from(elapsed) * u128::from(DENOMINATOR)) / u128::from(half_life_seconds);
-        if scaled >= 61 * u128::from(DENOMINATOR) {
-            return 1;
-        }
-
-        let k = (scaled / u128::from(DENOMINATOR)) as u64;
-        let frac = scaled % u128::from(DENOMINATOR);
-
-        if frac == 0 {
-            return process_pow2_neg_int(k);
-        }
-        let hi = process_pow2_neg_int(k);
-        let lo = if k < 60 {
-            process_pow2_neg_int(k + 1)
-        } else {
-            0
-        };
-
-        hi - ((hi - lo) * frac / u128::from(DENOMINATOR))
-    }
-
-    fn activity_factor(elapsed: u64, half_life_seconds: u64) -> u128 {
-        let decay = decay_factor(elapsed, half_life_seconds);
-        if decay >= WAD {
-            0
-        } else {
-            WAD - decay
-        }
-    }
-
-    pub fn calculate_reward(
-        reward_pool: u128,
-        weight: u64,
-        elapsed: u64,
-        total_weight: u128,
-        alpha: u64,
-        half_life_seconds: u64,
-    ) -> Result<u64> {
-        if reward_pool == 0 || weight == 0 || total_weight == 0 {
-            return Ok(0);
-        }
-        let activity = activity_factor(elapsed, half_life_seconds);
-        if activity == 0 {
-            return Ok(0);
-        }
-        let num = u128::from(weight)
-            .checked_mul(activity)
-            .ok_or(OracleError::MathOverflow)?
-            .checked_div(WAD)
-            .ok_or(OracleError::MathUnderflow)?;
-
-        let reward = u128::from(alpha)
-            .checked_mul(reward_pool)
-            .ok_or(OracleError::MathOverflow)?
-            .checked_mul(num)
-            .ok_or(OracleError::MathOverflow)?
-            .checked_div(total_weight)
-            .ok_or(OracleError::MathUnderflow)?;
+#[derive(Accounts)]
+pub struct Poke<'info> {
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+}
 
-        u64::try_from(reward).map_err(|_| error!(OracleError::MathOverflow))
-    }
+#[derive(Accounts)]
+pub struct ResetCircuitBreaker<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        has_one = authority @ OracleError::InvalidAuthority,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeOracle<'info> {
+#[instruction(submitter: Pubkey)]
+pub struct GrantSubmitter<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     pub authority: Signer<'info>,
-    pub weight_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        has_one = authority @ OracleError::InvalidAuthority,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
     #[account(
         init,
         payer = payer,
-        space = 8 + OracleState::SPACE,
-        seeds = [b"oracle", authority.key().as_ref(), weight_mint.key().as_ref()],
+        space = 8 + SubmitterPermit::SPACE,
+        seeds = [b"submitter", oracle_state.key().as_ref(), submitter.as_ref()],
         bump
     )]
+    pub submitter_permit: Account<'info, SubmitterPermit>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSubmitterPermit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
     pub oracle_state: Account<'info, OracleState>,
     #[account(
         init,
-        payer = payer,
-        associated_token::mint = weight_mint,
-        associated_token::authority = oracle_state,
+        payer = user,
+        space = 8 + SubmitterPermit::SPACE,
+        seeds = [b"submitter", oracle_state.key().as_ref(), user.key().as_ref()],
+        bump
     )]
-    pub oracle_vault: Account<'info, TokenAccount>,
+    pub submitter_permit: Account<'info, SubmitterPermit>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct FundOracle<'info> {
+pub struct RevokeSubmitter<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"submitter", oracle_state.key().as_ref(), submitter_permit.submitter.as_ref()],
+        bump = submitter_permit.bump,
+        has_one = payer,
+    )]
+    pub submitter_permit: Account<'info, SubmitterPermit>,
+    /// CHECK: receives the permit's rent; matched by `has_one`.
     #[account(mut)]
-    pub funder: Signer<'info>,
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"oracle", oracle_state.authority.as_ref(), oracle_state.weight_mint.as_ref()],
-        bump = oracle_state.bump
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
     )]
     pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), proposer.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == proposer.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::SPACE,
+        seeds = [b"proposal", oracle_state.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DepositTokens<'info> {
+pub struct VoteProposal<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
-    pub weight_mint: Account<'info, Mint>,
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        seeds = [b"user", oracle_state.key().as_ref(), voter.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == voter.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+    #[account(
+        mut,
+        constraint = proposal.oracle == oracle_state.key() @ OracleError::InvalidProposal
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// Exists once per voter, so a second vote fails on `init`.
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + ProposalVote::SPACE,
+        seeds = [b"proposal_vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub proposal_vote: Account<'info, ProposalVote>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
     #[account(
         mut,
-        seeds = [b"oracle", oracle_state.authority.as_ref(), oracle_state.weight_mint.as_ref()],
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
         bump = oracle_state.bump,
-        constraint = oracle_state.weight_mint == weight_mint.key()
     )]
     pub oracle_state: Account<'info, OracleState>,
     #[account(
         mut,
-        associated_token::mint = weight_mint,
-        associated_token::authority = user
+        constraint = proposal.oracle == oracle_state.key() @ OracleError::InvalidProposal
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct OpenAppeal<'info> {
+    #[account(mut)]
+    pub appellant: Signer<'info>,
+    #[account(
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    /// One open appeal per target; holds the bond until resolution.
+    #[account(
+        init,
+        payer = appellant,
+        space = 8 + Appeal::SPACE,
+        seeds = [b"appeal", oracle_state.key().as_ref(), appellant.key().as_ref()],
+        bump
+    )]
+    pub appeal: Account<'info, Appeal>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteAppeal<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        seeds = [b"user", oracle_state.key().as_ref(), voter.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.owner == voter.key()
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_state: Account<'info, UserState>,
     #[account(
         mut,
-        associated_token::mint = weight_mint,
-        associated_token::authority = oracle_state,
+        seeds = [b"appeal", oracle_state.key().as_ref(), appeal.appellant.as_ref()],
+        bump = appeal.bump,
     )]
-    pub oracle_vault: Account<'info, TokenAccount>,
+    pub appeal: Account<'info, Appeal>,
     #[account(
         init_if_needed,
-        payer = user,
-        space = 8 + UserState::SPACE,
-        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
-        bump,
-        constraint = user_state.owner == Pubkey::default() || user_state.owner == user.key()
+        payer = voter,
+        space = 8 + AppealVote::SPACE,
+        seeds = [b"appeal_vote", appeal.key().as_ref(), voter.key().as_ref()],
+        bump
     )]
-    pub user_state: Account<'info, UserState>,
-    pub token_program: Program<'info, Token>,
+    pub appeal_vote: Account<'info, AppealVote>,
     pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct WithdrawTokens<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub weight_mint: Account<'info, Mint>,
+pub struct ResolveAppeal<'info> {
     #[account(
         mut,
-        seeds = [b"oracle", oracle_state.authority.as_ref(), oracle_state.weight_mint.as_ref()],
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
         bump = oracle_state.bump,
-        constraint = oracle_state.weight_mint == weight_mint.key()
     )]
     pub oracle_state: Account<'info, OracleState>,
     #[account(
         mut,
-        associated_token::mint = weight_mint,
-        associated_token::authority = user
+        close = appellant,
+        seeds = [b"appeal", oracle_state.key().as_ref(), appeal.appellant.as_ref()],
+        bump = appeal.bump,
+    )]
+    pub appeal: Account<'info, Appeal>,
+    #[account(mut, address = appeal.appellant)]
+    pub appellant: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(record_sequence: u64)]
+pub struct OpenDispute<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+        constraint = oracle_state.stake_kind != StakeKind::NftCollection @ OracleError::NotTokenOracle
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub oracle_state: Account<'info, OracleState>,
+    /// The staker whose submission is disputed.
     #[account(
         mut,
-        associated_token::mint = weight_mint,
-        associated_token::authority = oracle_state,
+        seeds = [b"user", oracle_state.key().as_ref(), submitter_state.owner.as_ref()],
+        bump = submitter_state.bump,
     )]
-    pub oracle_vault: Account<'info, TokenAccount>,
+    pub submitter_state: Account<'info, UserState>,
     #[account(
         mut,
-        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
+        seeds = [b"history", oracle_state.key().as_ref()],
+        bump = price_history.bump,
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+    #[account(
+        init,
+        payer = disputer,
+        space = 8 + Dispute::SPACE,
+        seeds = [
+            b"dispute",
+            oracle_state.key().as_ref(),
+            submitter_state.owner.as_ref(),
+            record_sequence.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteDispute<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        seeds = [b"user", oracle_state.key().as_ref(), voter.key().as_ref()],
         bump = user_state.bump,
-        constraint = user_state.owner == user.key()
+        constraint = user_state.owner == voter.key()
     )]
     pub user_state: Account<'info, UserState>,
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        constraint = dispute.oracle == oracle_state.key() @ OracleError::InvalidDispute
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + DisputeVote::SPACE,
+        seeds = [b"dispute_vote", dispute.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub dispute_vote: Account<'info, DisputeVote>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SubmitValue<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
+        bump = oracle_state.bump,
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    #[account(
+        mut,
+        close = disputer,
+        constraint = dispute.oracle == oracle_state.key() @ OracleError::InvalidDispute
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut, address = dispute.disputer)]
+    pub disputer: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"user", oracle_state.key().as_ref(), dispute.submitter.as_ref()],
+        bump = submitter_state.bump,
+    )]
+    pub submitter_state: Account<'info, UserState>,
+    #[account(
+        mut,
+        seeds = [b"history", oracle_state.key().as_ref()],
+        bump = price_history.bump,
+    )]
+    pub price_history: Account<'info, PriceHistory>,
+    /// Token oracles pay slashed stake from the vault with these.
+    #[account(address = oracle_state.weight_mint)]
+    pub weight_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = oracle_vault.owner == oracle_state.key())]
+    pub oracle_vault: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        seeds = [b"oracle", oracle_state.authority.as_ref(), oracle_state.weight_mint.as_ref()],
-        bump = oracle_state.bump,
+        constraint = disputer_token_account.owner == dispute.disputer @ OracleError::InvalidDisputer
     )]
-    pub oracle_state: Account<'info, OracleState>,
+    pub disputer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// Native oracles pay slashed stake from here.
     #[account(
         mut,
-        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
-        bump = user_state.bump,
-        constraint = user_state.owner == user.key()
+        seeds = [b"native_vault", oracle_state.key().as_ref()],
+        bump = oracle_state.native_vault_bump
     )]
-    pub user_state: Account<'info, UserState>,
+    pub native_vault: Option<SystemAccount<'info>>,
+    /// Required when the submitter has stake delegated away, so it can be
+    /// slashed too.
+    #[account(
+        mut,
+        seeds = [b"delegation", oracle_state.key().as_ref(), dispute.submitter.as_ref()],
+        bump = submitter_delegation.bump,
+    )]
+    pub submitter_delegation: Option<Account<'info, Delegation>>,
+    /// The delegate's state, while that delegation is still active.
+    #[account(mut)]
+    pub delegate_state: Option<Account<'info, UserState>>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateUserVoteWeights<'info> {
-    #[account(mut)] 
-    pub user: Signer<'info>,
+pub struct CreateReceiptMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"oracle", oracle_state.authority.as_ref(), oracle_state.weight_mint.as_ref()],
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
         bump = oracle_state.bump,
+        has_one = authority @ OracleError::InvalidAuthority,
+        has_one = weight_mint,
+        constraint = oracle_state.stake_kind == StakeKind::Token @ OracleError::NotTokenOracle
     )]
     pub oracle_state: Account<'info, OracleState>,
+    #[account(mint::token_program = token_program)]
+    pub weight_mint: InterfaceAccount<'info, Mint>,
+    /// Frozen by the oracle for blacklisted holders, so it must never be
+    /// handed to another authority.
     #[account(
-        mut,
-        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
-        bump = user_state.bump,
-        constraint = user_state.owner == user.key()
+        init,
+        payer = payer,
+        seeds = [b"receipt_mint", oracle_state.key().as_ref()],
+        bump,
+        mint::decimals = weight_mint.decimals,
+        mint::authority = oracle_state,
+        mint::freeze_authority = oracle_state,
+        mint::token_program = token_program,
     )]
-    pub user_state: Account<'info, UserState>,
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Vote<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct SetReceiptsFrozen<'info> {
     #[account(
-        mut,
-        seeds = [b"oracle", oracle_state.authority.as_ref(), oracle_state.weight_mint.as_ref()],
+        seeds = [
+            OracleState::seed_prefix(&oracle_state),
+            oracle_state.authority.as_ref(),
+            OracleState::seed_mint(&oracle_state),
+        ],
         bump = oracle_state.bump,
     )]
     pub oracle_state: Account<'info, OracleState>,
+    #[account(address = oracle_state.receipt_mint @ OracleError::InvalidReceiptMint)]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
     #[account(
         mut,
-        seeds = [b"user", oracle_state.key().as_ref(), user.key().as_ref()],
-        bump = user_state.bump,
-        constraint = user_state.owner == user.key()
+        token::mint = receipt_mint,
+        token::token_program = token_program,
     )]
-    pub user_state: Account<'info, UserState>,
+    pub receipt_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[account]
@@ -853,52 +4507,206 @@ pub struct OracleState {
     pub weight_mint: Pubkey,
     pub reward_bps: u64,
     pub half_life_seconds: u64,
+    pub decay_model: DecayModel,
+    /// Vote weight a decision's tally must exceed.
     pub quorum: u64,
+    /// Quorum as a share of `total_vote_weight`, replacing `quorum` when
+    /// nonzero.
+    pub quorum_bps: u64,
     pub deposit_locking_period: u64,
+    /// Unbonding time for `request_withdrawal`; 0 allows direct withdrawals.
     pub withdrawal_locking_period: u64,
+    /// Share of an early withdrawal kept as a penalty; 0 disables early exit.
+    pub early_exit_penalty_bps: u64,
+    /// Token account that receives early-exit penalties; a wallet on native
+    /// oracles.
+    pub treasury: Pubkey,
+    /// Longest vote-escrow lock a staker can choose; 0 disables boosting.
+    pub ve_max_lock_seconds: u64,
+    /// Extra weight, in `DENOMINATOR` units, for a lock of the full length.
+    /// At most `MAX_VE_BOOST_BPS`.
+    pub ve_max_boost_bps: u64,
+    /// Absolute ceiling on a single user's effective weight; 0 disables.
+    pub max_user_weight: u64,
+    /// Ceiling as a share of `total_deposited_tokens`; 0 disables.
+    pub max_user_weight_bps: u64,
+    pub weighting_mode: WeightingMode,
+    /// Unlocked plus delegated-in tokens needed to call `submit_value`.
+    pub min_submit_stake: u64,
+    /// Vote weight needed to vote, read at the decision's snapshot, and to
+    /// open a proposal.
+    pub min_vote_stake: u64,
+    /// Seconds a user must wait between their own submissions.
+    pub min_submission_interval: u64,
+    pub access_mode: AccessMode,
+    /// Seconds a proposal stays open for votes; 0 disables proposals.
+    pub proposal_voting_period: u64,
+    /// Seconds between the close of voting and a passed proposal's execution.
+    pub proposal_execution_delay: u64,
+    /// Seconds a passed proposal stays executable; required with governance.
+    pub proposal_execution_grace: u64,
+    /// Lamports a blacklisted user stakes on an appeal.
+    pub appeal_bond: u64,
+    /// Seconds an appeal stays open for votes; 0 disables appeals.
+    pub appeal_voting_period: u64,
+    /// Seconds a target must stay blacklisted before it may appeal.
+    pub min_blacklist_duration: u64,
+    /// Lamports a disputer stakes on a challenge to a reported value;
+    /// required once disputes are enabled.
+    pub dispute_bond: u64,
+    /// Seconds a dispute stays open for votes; 0 disables disputes. Not
+    /// available on oracles with a receipt mint.
+    pub dispute_voting_period: u64,
+    /// Share of the submitter's stake paid to the disputer when a dispute is
+    /// upheld.
+    pub dispute_slash_bps: u64,
     pub alpha: u64,
+    pub heartbeat_seconds: u64,
+    pub status: OracleStatus,
+    pub max_update_deviation_bps: u64,
+    pub max_window_deviation_bps: u64,
+    pub deviation_window_seconds: u64,
+    pub breaker_reset_time: i64,
     pub aggregated_value: i128,
     pub latest_value: i128,
     pub aggregated_weight: u128,
+    pub weighted_sum: I256,
     pub last_submission_time: i64,
     pub last_timestamp: i64,
     pub total_deposited_tokens: u64,
+    /// Sum of every staker's `vote_weight`, in the same units as the vote
+    /// tallies; `quorum_bps` is a share of it.
+    pub total_vote_weight: u64,
+    /// `total_vote_weight` before the changes made in
+    /// `vote_weight_updated_slot`.
+    pub prior_vote_weight: u64,
+    pub vote_weight_updated_slot: u64,
+    pub stake_kind: StakeKind,
+    /// Bump of the lamport vault; only set on native oracles.
+    pub native_vault_bump: u8,
+    /// Lamports earned by delegators but not yet claimed; excluded from the
+    /// reward pool.
+    pub delegator_rewards_reserved: u64,
+    /// Liquid receipt mint tracking staked tokens; default when disabled.
+    pub receipt_mint: Pubkey,
+    /// Id the next proposal must use.
+    pub proposal_count: u64,
     pub bump: u8,
     pub name: String,
     pub description: String,
-    pub price_history: Vec<PriceRecord>,
     pub targets: Vec<TargetVotes>,
 }
 
 impl OracleState {
     pub const MAX_NAME_LEN: usize = 64;
     pub const MAX_DESCRIPTION_LEN: usize = 256;
-    pub const MAX_HISTORY_CAPACITY: usize = MAX_HISTORY_ENTRIES;
     pub const MAX_TARGET_CAPACITY: usize = MAX_TARGET_RECORDS;
-    const PRICE_RECORD_SIZE: usize = 8 + 16 + 16;
-    const TARGET_VOTES_SIZE: usize = 32 + 8 + 8 + 1;
+    const TARGET_VOTES_SIZE: usize = 32 + 8 + 8 + 1 + 8 + 4 + 8;
     pub const SPACE: usize =
         32 + // authority
         32 + // weight_mint
         8 + // reward_bps
         8 + // half_life_seconds
+        9 + // decay_model
         8 + // quorum
+        8 + // quorum_bps
         8 + // deposit_locking_period
         8 + // withdrawal_locking_period
+        8 + // early_exit_penalty_bps
+        32 + // treasury
+        8 + // ve_max_lock_seconds
+        8 + // ve_max_boost_bps
+        8 + // max_user_weight
+        8 + // max_user_weight_bps
+        1 + // weighting_mode
+        8 + // min_submit_stake
+        8 + // min_vote_stake
+        8 + // min_submission_interval
+        1 + // access_mode
+        8 + // proposal_voting_period
+        8 + // proposal_execution_delay
+        8 + // proposal_execution_grace
+        8 + // appeal_bond
+        8 + // appeal_voting_period
+        8 + // min_blacklist_duration
+        8 + // dispute_bond
+        8 + // dispute_voting_period
+        8 + // dispute_slash_bps
         8 + // alpha
+        8 + // heartbeat_seconds
+        1 + // status
+        8 + // max_update_deviation_bps
+        8 + // max_window_deviation_bps
+        8 + // deviation_window_seconds
+        8 + // breaker_reset_time
         16 + // aggregated_value
         16 + // latest_value
         16 + // aggregated_weight
+        33 + // weighted_sum
         8 + // last_submission_time
         8 + // last_timestamp
         8 + // total_deposited_tokens
+        8 + // total_vote_weight
+        8 + // prior_vote_weight
+        8 + // vote_weight_updated_slot
+        1 + // stake_kind
+        1 + // native_vault_bump
+        8 + // delegator_rewards_reserved
+        32 + // receipt_mint
+        8 + // proposal_count
         1 + // bump
         4 + Self::MAX_NAME_LEN +
         4 + Self::MAX_DESCRIPTION_LEN +
-        4 + Self::MAX_HISTORY_CAPACITY * Self::PRICE_RECORD_SIZE +
         4 + Self::MAX_TARGET_CAPACITY * Self::TARGET_VOTES_SIZE +
         64; // buffer for future extensions
 
+    fn is_native(&self) -> bool {
+        self.stake_kind == StakeKind::Native
+    }
+
+    /// First seed of the oracle PDA. Native oracles have no mint to key on,
+    /// so they get their own prefix.
+    fn seed_prefix(&self) -> &'static [u8] {
+        match self.stake_kind {
+            StakeKind::Native => b"native_oracle",
+            StakeKind::Token | StakeKind::NftCollection => b"oracle",
+        }
+    }
+
+    /// Last seed of the oracle PDA: the weight mint, or nothing for native
+    /// oracles. An empty seed leaves the derivation unchanged, so native
+    /// oracles still resolve to `[b"native_oracle", authority]`.
+    fn seed_mint(&self) -> &[u8] {
+        match self.stake_kind {
+            StakeKind::Native => &[],
+            StakeKind::Token | StakeKind::NftCollection => self.weight_mint.as_ref(),
+        }
+    }
+
+    fn signer_seeds<'a>(&'a self, bump_seed: &'a [u8; 1]) -> [&'a [u8]; 4] {
+        [
+            self.seed_prefix(),
+            self.authority.as_ref(),
+            self.seed_mint(),
+            bump_seed,
+        ]
+    }
+
+    /// Largest effective weight any single user may contribute.
+    fn weight_cap(&self) -> u64 {
+        let mut cap = match self.max_user_weight {
+            0 => u64::MAX,
+            limit => limit,
+        };
+        if self.max_user_weight_bps > 0 {
+            let share = u128::from(self.total_deposited_tokens)
+                * u128::from(self.max_user_weight_bps)
+                / u128::from(DENOMINATOR);
+            cap = cap.min(u64::try_from(share).unwrap_or(u64::MAX));
+        }
+        cap
+    }
+
     fn is_blacklisted(&self, target: &Pubkey) -> bool {
         self.targets
             .iter()
@@ -907,7 +4715,55 @@ impl OracleState {
             .unwrap_or(false)
     }
 
-    fn upsert_target(&mut self, target: Pubkey) -> Result<usize> {
+    /// Mirror of the blacklist rule: whitelist votes lead and the total
+    /// clears quorum.
+    fn is_whitelisted(&self, target: &Pubkey, slot: u64) -> bool {
+        let quorum = self.quorum_threshold(slot);
+        self.targets
+            .iter()
+            .find(|record| &record.target == target)
+            .is_some_and(|record| {
+                record.whitelist_votes > record.blacklist_votes
+                    && record.blacklist_votes.saturating_add(record.whitelist_votes) > quorum
+            })
+    }
+
+    /// Returns the reference value that `candidate` moves too far from, if
+    /// any. Only history recorded after the last breaker reset is used, so the
+    /// feed can settle at a new level once the authority resumes it.
+    fn breached_deviation(
+        &self,
+        history: &PriceHistory,
+        candidate: i128,
+        now: i64,
+    ) -> Option<i128> {
+        let since_reset = |record: &&PriceRecord| record.timestamp >= self.breaker_reset_time;
+
+        if self.max_update_deviation_bps > 0 {
+            if let Some(last) = history.records.last().filter(since_reset) {
+                if exceeds_deviation(last.aggregated_value, candidate, self.max_update_deviation_bps) {
+                    return Some(last.aggregated_value);
+                }
+            }
+        }
+
+        if self.max_window_deviation_bps > 0 {
+            let window_start = now.saturating_sub(self.deviation_window_seconds as i64);
+            return history
+                .records
+                .iter()
+                .filter(since_reset)
+                .filter(|record| record.timestamp >= window_start)
+                .map(|record| record.aggregated_value)
+                .find(|&reference| {
+                    exceeds_deviation(reference, candidate, self.max_window_deviation_bps)
+                });
+        }
+
+        None
+    }
+
+    fn upsert_target(&mut self, target: Pubkey, slot: u64) -> Result<usize> {
         if let Some((index, _)) = self
             .targets
             .iter()
@@ -921,80 +4777,531 @@ impl OracleState {
             self.targets.len() < MAX_TARGET_RECORDS,
             OracleError::TooManyTargets
         );
-        self.targets.push(TargetVotes::new(target));
+        self.targets.push(TargetVotes::new(target, slot));
         Ok(self.targets.len() - 1)
     }
 
-    fn refresh_blacklist_status(&mut self, idx: usize) -> bool {
-        let record = &mut self.targets[idx];
-        let total_votes = record
-            .blacklist_votes
-            .saturating_add(record.whitelist_votes);
-        let should_blacklist =
-            record.blacklist_votes > record.whitelist_votes && total_votes > self.quorum;
-        let changed = record.is_blacklisted != should_blacklist;
-        record.is_blacklisted = should_blacklist;
-        changed
-    }
+    /// Vote total a decision must exceed. With `quorum_bps` set it tracks
+    /// total vote weight, so statuses are re-evaluated as stake moves.
+    /// Weight moved during the current slot is not counted yet, so stake
+    /// deposited and withdrawn around a refresh in one transaction cannot
+    /// swing a decision.
+    fn quorum_threshold(&self, slot: u64) -> u64 {
+        if self.quorum_bps == 0 {
+            return self.quorum;
+        }
+        let total = if slot == self.vote_weight_updated_slot {
+            self.prior_vote_weight
+        } else {
+            self.total_vote_weight
+        };
+        (u128::from(total) * u128::from(self.quorum_bps) / u128::from(DENOMINATOR)) as u64
+    }
+
+    /// Swaps a staker's previous vote weight for `new_weight` in
+    /// `total_vote_weight`.
+    fn update_total_vote_weight(&mut self, old_weight: u64, new_weight: u64, slot: u64) -> Result<()> {
+        if slot != self.vote_weight_updated_slot {
+            self.prior_vote_weight = self.total_vote_weight;
+            self.vote_weight_updated_slot = slot;
+        }
+        self.total_vote_weight = self
+            .total_vote_weight
+            .checked_sub(old_weight)
+            .ok_or(OracleError::MathUnderflow)?
+            .checked_add(new_weight)
+            .ok_or(OracleError::MathOverflow)?;
+        Ok(())
+    }
+
+    fn refresh_blacklist_status(&mut self, idx: usize, now: i64, slot: u64) -> bool {
+        let quorum = self.quorum_threshold(slot);
+        let record = &mut self.targets[idx];
+        let blacklist_votes = record.blacklist_votes;
+        let total_votes = blacklist_votes.saturating_add(record.whitelist_votes);
+        let should_blacklist =
+            blacklist_votes > record.whitelist_votes && total_votes > quorum;
+        let changed = record.is_blacklisted != should_blacklist;
+        if changed && should_blacklist {
+            record.blacklisted_at = now;
+        }
+        record.is_blacklisted = should_blacklist;
+        changed
+    }
+
+    fn blacklisted_at(&self, target: &Pubkey) -> Option<i64> {
+        self.targets
+            .iter()
+            .find(|record| &record.target == target && record.is_blacklisted)
+            .map(|record| record.blacklisted_at)
+    }
+}
+
+/// Rolling log of accepted submissions, oldest first. Kept in its own PDA so
+/// `OracleState` stays well under the account size limit.
+#[account]
+pub struct PriceHistory {
+    pub oracle: Pubkey,
+    pub records: Vec<PriceRecord>,
+    /// Sequence number the next record will be given.
+    pub next_sequence: u64,
+    pub bump: u8,
+}
+
+impl PriceHistory {
+    pub const MAX_RECORDS: usize = MAX_HISTORY_ENTRIES;
+    const PRICE_RECORD_SIZE: usize = 8 + 8 + 16 + 16 + 1 + 8 + 1;
+    pub const SPACE: usize =
+        32 + // oracle
+        4 + Self::MAX_RECORDS * Self::PRICE_RECORD_SIZE + // records
+        8 + // next_sequence
+        1; // bump
+
+    /// Index of the entry still holding `user_state`'s last submission, if it
+    /// has not rotated out.
+    fn live_record(&self, user_state: &UserState) -> Option<usize> {
+        let sequence = user_state.recent_submissions.last()?.sequence;
+        self.records
+            .iter()
+            .rposition(|record| !record.superseded && record.sequence == sequence)
+    }
+
+    /// Live entries as `(value, weight, age)` for `accumulator::windowed`.
+    fn contributions(&self, now: i64) -> impl Iterator<Item = (i128, u64, u64)> + '_ {
+        self.records
+            .iter()
+            .filter(|record| !record.superseded)
+            .map(move |record| {
+                (
+                    record.latest_value,
+                    record.weight,
+                    time_difference(now, record.timestamp),
+                )
+            })
+    }
+}
+
+#[account]
+pub struct UserState {
+    pub oracle: Pubkey,
+    pub owner: Pubkey,
+    pub locked_tokens: u64,
+    pub unlocked_tokens: u64,
+    pub last_operation_timestamp: i64,
+    pub last_submission_time: i64,
+    pub last_submitted_price: i128,
+    pub weight: u64,
+    /// End of the vote-escrow lock chosen at deposit; no withdrawals before it.
+    pub ve_lock_end: i64,
+    /// Stake other users have delegated to this reporter.
+    pub delegated_in: u64,
+    /// Own stake held in this user's `Delegation`, until the undelegation
+    /// completes.
+    pub delegated_out: u64,
+    /// Share of delegators' rewards this reporter keeps, in `DENOMINATOR` units.
+    pub commission_bps: u64,
+    /// Cumulative delegator reward per delegated token, scaled by `WAD`.
+    pub delegation_reward_index: u128,
+    /// NFTs held in escrow for this user on collection-weighted oracles,
+    /// including any still locked.
+    pub nft_count: u64,
+    /// Hot key allowed to submit and vote on the owner's behalf.
+    pub submitter: Pubkey,
+    pub initialized: bool,
+    pub bump: u8,
+    pub blacklist_votes: Vec<UserVote>,
+    pub whitelist_votes: Vec<UserVote>,
+    pub deposit_lots: Vec<DepositLot>,
+    /// Tokens waiting out the unbonding period. They no longer carry weight
+    /// and are already excluded from `total_deposited_tokens`.
+    pub pending_withdrawals: Vec<PendingWithdrawal>,
+    /// `vote_weight` after each change, oldest first; see `checkpoints`.
+    pub stake_checkpoints: Vec<StakeCheckpoint>,
+    /// Newest submissions, oldest first; lets a dispute name its submitter.
+    pub recent_submissions: Vec<SubmissionRecord>,
+    /// Open disputes against this user's submissions. Stake cannot leave
+    /// while any are pending.
+    pub open_disputes: u64,
+    /// Weight last counted into the oracle's `total_vote_weight`.
+    pub vote_weight: u64,
+}
+
+impl UserState {
+    pub const MAX_VOTES: usize = MAX_USER_VOTES;
+    pub const MAX_LOTS: usize = MAX_DEPOSIT_LOTS;
+    const USER_VOTE_SIZE: usize = 32 + 8 + 4;
+    pub const MAX_PENDING: usize = MAX_PENDING_WITHDRAWALS;
+    const DEPOSIT_LOT_SIZE: usize = 8 + 8;
+    const PENDING_WITHDRAWAL_SIZE: usize = 8 + 8;
+    pub const MAX_CHECKPOINTS: usize = MAX_STAKE_CHECKPOINTS;
+    const STAKE_CHECKPOINT_SIZE: usize = 8 + 8;
+    pub const MAX_SUBMISSIONS: usize = MAX_RECENT_SUBMISSIONS;
+    const SUBMISSION_RECORD_SIZE: usize = 8 + 8 + 16;
+    pub const SPACE: usize =
+        32 + // oracle
+        32 + // owner
+        8 + // locked_tokens
+        8 + // unlocked_tokens
+        8 + // last_operation_timestamp
+        8 + // last_submission_time
+        16 + // last_submitted_price
+        8 + // weight
+        8 + // ve_lock_end
+        8 + // delegated_in
+        8 + // delegated_out
+        8 + // commission_bps
+        16 + // delegation_reward_index
+        8 + // nft_count
+        32 + // submitter
+        1 + // initialized
+        1 + // bump
+        4 + Self::MAX_VOTES * Self::USER_VOTE_SIZE + // blacklist_votes
+        4 + Self::MAX_VOTES * Self::USER_VOTE_SIZE + // whitelist_votes
+        4 + Self::MAX_LOTS * Self::DEPOSIT_LOT_SIZE + // deposit_lots
+        4 + Self::MAX_PENDING * Self::PENDING_WITHDRAWAL_SIZE + // pending_withdrawals
+        4 + Self::MAX_CHECKPOINTS * Self::STAKE_CHECKPOINT_SIZE + // stake_checkpoints
+        4 + Self::MAX_SUBMISSIONS * Self::SUBMISSION_RECORD_SIZE + // recent_submissions
+        8 + // open_disputes
+        8 + // vote_weight
+        32; // buffer for future fields
+
+    fn can_submit(&self, signer: &Pubkey) -> bool {
+        self.owner == *signer || (self.submitter != Pubkey::default() && self.submitter == *signer)
+    }
+
+    fn has_stake(&self) -> bool {
+        self.unlocked_tokens > 0 || self.delegated_in > 0
+    }
+
+    fn staked_tokens(&self) -> u64 {
+        self.unlocked_tokens.saturating_add(self.delegated_in)
+    }
+
+    fn checkpoint_weight(&mut self, slot: u64) {
+        checkpoints::record(&mut self.stake_checkpoints, slot, self.vote_weight);
+    }
+
+    fn weight_before(&self, slot: u64) -> u64 {
+        checkpoints::weight_before(&self.stake_checkpoints, slot)
+    }
+
+    fn record_submission(&mut self, sequence: u64, timestamp: i64, value: i128) {
+        if self.recent_submissions.len() == MAX_RECENT_SUBMISSIONS {
+            self.recent_submissions.remove(0);
+        }
+        self.recent_submissions.push(SubmissionRecord {
+            sequence,
+            timestamp,
+            value,
+        });
+    }
+
+    fn ensure_initialized(&mut self, oracle: Pubkey, owner: Pubkey, bump: u8) -> Result<()> {
+        if !self.initialized {
+            self.oracle = oracle;
+            self.owner = owner;
+            self.initialized = true;
+            self.bump = bump;
+        } else {
+            require!(
+                self.oracle == oracle && self.owner == owner,
+                OracleError::InvalidAuthority
+            );
+        }
+        Ok(())
+    }
+
+    /// Records a deposit that unlocks at `unlock_time`. Deposits made in the
+    /// same second share a lot.
+    fn add_deposit_lot(&mut self, amount: u64, unlock_time: i64) -> Result<()> {
+        if let Some(lot) = self
+            .deposit_lots
+            .last_mut()
+            .filter(|lot| lot.unlock_time == unlock_time)
+        {
+            lot.amount = lot
+                .amount
+                .checked_add(amount)
+                .ok_or(OracleError::MathOverflow)?;
+            return Ok(());
+        }
+        require!(
+            self.deposit_lots.len() < MAX_DEPOSIT_LOTS,
+            OracleError::TooManyDepositLots
+        );
+        self.deposit_lots.push(DepositLot { amount, unlock_time });
+        Ok(())
+    }
+
+    /// Removes `amount` from locked lots, most recent first.
+    fn take_from_locked_lots(&mut self, mut amount: u64) -> Result<()> {
+        self.locked_tokens = self
+            .locked_tokens
+            .checked_sub(amount)
+            .ok_or(OracleError::MathUnderflow)?;
+        while amount > 0 {
+            let lot = self
+                .deposit_lots
+                .last_mut()
+                .ok_or(OracleError::MathUnderflow)?;
+            let taken = amount.min(lot.amount);
+            lot.amount -= taken;
+            amount -= taken;
+            if lot.amount == 0 {
+                self.deposit_lots.pop();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stake a user has handed to a reporter. Seeded by the delegator, so each
+/// user delegates to one reporter at a time.
+#[account]
+pub struct Delegation {
+    pub oracle: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    /// The delegate's `delegation_reward_index` at the last payout.
+    pub reward_index: u128,
+    /// When undelegated tokens return to the delegator; 0 while active.
+    pub available_at: i64,
+    pub bump: u8,
+}
+
+impl Delegation {
+    pub const SPACE: usize =
+        32 + // oracle
+        32 + // delegator
+        32 + // delegate
+        8 + // amount
+        16 + // reward_index
+        8 + // available_at
+        1; // bump
+
+    fn pending_reward(&self, reward_index: u128) -> Result<u64> {
+        let accrued = u128::from(self.amount)
+            .checked_mul(reward_index.saturating_sub(self.reward_index))
+            .ok_or(OracleError::MathOverflow)?
+            / WAD;
+        u64::try_from(accrued).map_err(|_| error!(OracleError::MathOverflow))
+    }
+}
+
+/// When one escrowed NFT may be withdrawn, set from the deposit locking
+/// period at deposit.
+#[account]
+pub struct NftLock {
+    pub unlock_time: i64,
+    pub bump: u8,
+}
+
+impl NftLock {
+    pub const SPACE: usize =
+        8 + // unlock_time
+        1; // bump
+}
+
+/// Lets `submitter` report to a permissioned oracle. Granted by the authority
+/// or claimed by a user the whitelist vote has approved.
+#[account]
+pub struct SubmitterPermit {
+    pub oracle: Pubkey,
+    pub submitter: Pubkey,
+    /// Paid the rent and receives it back on revocation.
+    pub payer: Pubkey,
+    pub via_vote: bool,
+    pub bump: u8,
+}
+
+impl SubmitterPermit {
+    pub const SPACE: usize =
+        32 + // oracle
+        32 + // submitter
+        32 + // payer
+        1 + // via_vote
+        1; // bump
+}
+
+/// A stake-voted change to oracle parameters, executable by anyone once
+/// voting has closed, the delay has passed and it cleared quorum.
+#[account]
+pub struct Proposal {
+    pub oracle: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub update: ParameterUpdate,
+    /// Votes count vote weight held before this slot.
+    pub snapshot_slot: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+    /// A passed proposal left unexecuted until this time lapses, so stale
+    /// parameters cannot be applied long after the vote.
+    pub expires_at: i64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const SPACE: usize =
+        32 + // oracle
+        8 + // id
+        32 + // proposer
+        ParameterUpdate::SPACE + // update
+        8 + // snapshot_slot
+        8 + // yes_votes
+        8 + // no_votes
+        8 + // voting_ends_at
+        8 + // executable_at
+        8 + // expires_at
+        1 + // executed
+        1; // bump
+
+    /// Same rule as the blacklist vote: the majority wins once the total
+    /// clears quorum.
+    fn passed(&self, quorum: u64) -> bool {
+        self.yes_votes > self.no_votes && self.yes_votes.saturating_add(self.no_votes) > quorum
+    }
+}
+
+/// A bonded challenge to one submission in the price history.
+#[account]
+pub struct Dispute {
+    pub oracle: Pubkey,
+    pub disputer: Pubkey,
+    pub submitter: Pubkey,
+    /// Sequence number of the disputed `PriceRecord`.
+    pub record_sequence: u64,
+    pub record_timestamp: i64,
+    pub disputed_value: i128,
+    pub proposed_value: i128,
+    /// Lamports returned if the dispute is upheld and forfeited otherwise.
+    pub bond: u64,
+    /// Votes count vote weight held before this slot.
+    pub snapshot_slot: u64,
+    pub support_votes: u64,
+    pub oppose_votes: u64,
+    pub voting_ends_at: i64,
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const SPACE: usize =
+        32 + // oracle
+        32 + // disputer
+        32 + // submitter
+        8 + // record_sequence
+        8 + // record_timestamp
+        16 + // disputed_value
+        16 + // proposed_value
+        8 + // bond
+        8 + // snapshot_slot
+        8 + // support_votes
+        8 + // oppose_votes
+        8 + // voting_ends_at
+        1; // bump
+}
+
+#[account]
+pub struct DisputeVote {
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+impl DisputeVote {
+    pub const SPACE: usize =
+        32 + // voter
+        1 + // support
+        8 + // weight
+        1; // bump
+}
+
+/// A blacklisted user's bonded request for a fresh vote on their status.
+#[account]
+pub struct Appeal {
+    pub oracle: Pubkey,
+    pub appellant: Pubkey,
+    /// Lamports returned if the appeal is granted and forfeited otherwise.
+    pub bond: u64,
+    /// Votes count vote weight held before this slot.
+    pub snapshot_slot: u64,
+    pub support_votes: u64,
+    pub oppose_votes: u64,
+    pub voting_ends_at: i64,
+    pub bump: u8,
+}
+
+impl Appeal {
+    pub const SPACE: usize =
+        32 + // oracle
+        32 + // appellant
+        8 + // bond
+        8 + // snapshot_slot
+        8 + // support_votes
+        8 + // oppose_votes
+        8 + // voting_ends_at
+        1; // bump
+}
+
+#[account]
+pub struct AppealVote {
+    pub voter: Pubkey,
+    /// `voting_ends_at` of the appeal round this vote was cast in.
+    pub voting_ends_at: i64,
+    pub bump: u8,
+}
+
+impl AppealVote {
+    pub const SPACE: usize =
+        32 + // voter
+        8 + // voting_ends_at
+        1; // bump
 }
 
 #[account]
-pub struct UserState {
-    pub oracle: Pubkey,
-    pub owner: Pubkey,
-    pub locked_tokens: u64,
-    pub unlocked_tokens: u64,
-    pub deposit_timestamp: i64,
-    pub last_operation_timestamp: i64,
-    pub last_submission_time: i64,
-    pub last_submitted_price: i128,
+pub struct ProposalVote {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
     pub weight: u64,
-    pub initialized: bool,
     pub bump: u8,
-    pub blacklist_votes: Vec<UserVote>,
-    pub whitelist_votes: Vec<UserVote>,
 }
 
-impl UserState {
-    pub const MAX_VOTES: usize = MAX_USER_VOTES;
-    const USER_VOTE_SIZE: usize = 32 + 8;
+impl ProposalVote {
     pub const SPACE: usize =
-        32 + // oracle
-        32 + // owner
-        8 + // locked_tokens
-        8 + // unlocked_tokens
-        8 + // deposit_timestamp
-        8 + // last_operation_timestamp
-        8 + // last_submission_time
-        16 + // last_submitted_price
+        32 + // proposal
+        32 + // voter
+        1 + // support
         8 + // weight
-        1 + // initialized
-        1 + // bump
-        4 + Self::MAX_VOTES * Self::USER_VOTE_SIZE + // blacklist_votes
-        4 + Self::MAX_VOTES * Self::USER_VOTE_SIZE + // whitelist_votes
-        32; // buffer for future fields
-
-    fn ensure_initialized(&mut self, oracle: Pubkey, owner: Pubkey, bump: u8) -> Result<()> {
-        if !self.initialized {
-            self.oracle = oracle;
-            self.owner = owner;
-            self.initialized = true;
-            self.bump = bump;
-        } else {
-            require!(
-                self.oracle == oracle && self.owner == owner,
-                OracleError::InvalidAuthority
-            );
-        }
-        Ok(())
-    }
+        1; // bump
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct PriceRecord {
+    /// Position of this record among all records ever pushed, so a dispute
+    /// can name it even when other fields repeat.
+    pub sequence: u64,
     pub timestamp: i64,
     pub aggregated_value: i128,
     pub latest_value: i128,
+    pub dispute: DisputeOutcome,
+    /// Effective weight the submitter contributed.
+    pub weight: u64,
+    /// Set once the same staker submits again and this value stops counting.
+    pub superseded: bool,
+}
+
+/// Signed 256-bit integer in sign-magnitude form, see `accumulator`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct I256 {
+    pub negative: bool,
+    pub hi: u128,
+    pub lo: u128,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -1003,23 +5310,62 @@ pub struct TargetVotes {
     pub blacklist_votes: u64,
     pub whitelist_votes: u64,
     pub is_blacklisted: bool,
+    /// When the target was last blacklisted.
+    pub blacklisted_at: i64,
+    /// Bumped by each granted appeal. Blacklist votes cast in an earlier
+    /// round were cleared from the tally and no longer count.
+    pub pardon_round: u32,
+    /// Votes count vote weight held before this slot: the first vote on the
+    /// target, or the last granted appeal.
+    pub snapshot_slot: u64,
 }
 
 impl TargetVotes {
-    fn new(target: Pubkey) -> Self {
+    fn new(target: Pubkey, snapshot_slot: u64) -> Self {
         Self {
             target,
             blacklist_votes: 0,
             whitelist_votes: 0,
             is_blacklisted: false,
+            blacklisted_at: 0,
+            pardon_round: 0,
+            snapshot_slot,
         }
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DepositLot {
+    pub amount: u64,
+    pub unlock_time: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingWithdrawal {
+    pub amount: u64,
+    pub available_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubmissionRecord {
+    /// Sequence number of the matching `PriceRecord`.
+    pub sequence: u64,
+    pub timestamp: i64,
+    pub value: i128,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StakeCheckpoint {
+    pub slot: u64,
+    pub weight: u64,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct UserVote {
     pub target: Pubkey,
     pub weight: u64,
+    /// The target's `pardon_round` when the vote was cast.
+    pub round: u32,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -1028,10 +5374,320 @@ pub struct InitializeOracleParams {
     pub description: String,
     pub reward_bps: u64,
     pub half_life_seconds: u64,
+    /// Vote weight a decision's tally must exceed.
     pub quorum: u64,
+    /// Share of total vote weight, in bps, used instead of `quorum` when
+    /// nonzero.
+    pub quorum_bps: u64,
     pub deposit_locking_period: u64,
     pub withdrawal_locking_period: u64,
     pub alpha: u64,
+    pub decay_model: DecayModel,
+    pub early_exit_penalty_bps: u64,
+    pub treasury: Pubkey,
+    pub ve_max_lock_seconds: u64,
+    pub ve_max_boost_bps: u64,
+    pub max_user_weight: u64,
+    pub max_user_weight_bps: u64,
+    pub weighting_mode: WeightingMode,
+    /// Unlocked plus delegated-in tokens needed to call `submit_value`.
+    pub min_submit_stake: u64,
+    /// Vote weight needed to vote, read at the decision's snapshot, and to
+    /// open a proposal.
+    pub min_vote_stake: u64,
+    /// Seconds a user must wait between their own submissions.
+    pub min_submission_interval: u64,
+    pub access_mode: AccessMode,
+    /// Seconds a proposal stays open for votes; 0 disables proposals.
+    pub proposal_voting_period: u64,
+    /// Seconds between the close of voting and a passed proposal's execution.
+    pub proposal_execution_delay: u64,
+    /// Seconds a passed proposal stays executable; required with governance.
+    pub proposal_execution_grace: u64,
+    /// Lamports a blacklisted user stakes on an appeal.
+    pub appeal_bond: u64,
+    /// Seconds an appeal stays open for votes; 0 disables appeals.
+    pub appeal_voting_period: u64,
+    /// Seconds a target must stay blacklisted before it may appeal.
+    pub min_blacklist_duration: u64,
+    /// Lamports a disputer stakes on a challenge to a reported value;
+    /// required once disputes are enabled.
+    pub dispute_bond: u64,
+    /// Seconds a dispute stays open for votes; 0 disables disputes. Not
+    /// available on oracles with a receipt mint.
+    pub dispute_voting_period: u64,
+    /// Share of the submitter's stake paid to the disputer when a dispute is
+    /// upheld.
+    pub dispute_slash_bps: u64,
+    pub heartbeat_seconds: u64,
+    pub max_update_deviation_bps: u64,
+    pub max_window_deviation_bps: u64,
+    pub deviation_window_seconds: u64,
+}
+
+/// Parameters a proposal may change; `None` leaves a field as it is. Mint,
+/// authority and vote-escrow settings are fixed for the oracle's lifetime.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ParameterUpdate {
+    pub reward_bps: Option<u64>,
+    pub half_life_seconds: Option<u64>,
+    pub decay_model: Option<DecayModel>,
+    pub quorum: Option<u64>,
+    pub quorum_bps: Option<u64>,
+    pub deposit_locking_period: Option<u64>,
+    pub withdrawal_locking_period: Option<u64>,
+    pub early_exit_penalty_bps: Option<u64>,
+    pub treasury: Option<Pubkey>,
+    pub alpha: Option<u64>,
+    pub heartbeat_seconds: Option<u64>,
+    pub max_update_deviation_bps: Option<u64>,
+    pub max_window_deviation_bps: Option<u64>,
+    pub deviation_window_seconds: Option<u64>,
+    pub max_user_weight: Option<u64>,
+    pub max_user_weight_bps: Option<u64>,
+    pub weighting_mode: Option<WeightingMode>,
+    pub min_submit_stake: Option<u64>,
+    pub min_vote_stake: Option<u64>,
+    pub min_submission_interval: Option<u64>,
+    pub access_mode: Option<AccessMode>,
+    pub proposal_voting_period: Option<u64>,
+    pub proposal_execution_delay: Option<u64>,
+    pub proposal_execution_grace: Option<u64>,
+    pub appeal_bond: Option<u64>,
+    pub appeal_voting_period: Option<u64>,
+    pub min_blacklist_duration: Option<u64>,
+    pub dispute_bond: Option<u64>,
+    pub dispute_voting_period: Option<u64>,
+    pub dispute_slash_bps: Option<u64>,
+    /// Resumes a halted oracle, replacing the authority's breaker reset.
+    pub reset_circuit_breaker: bool,
+}
+
+impl ParameterUpdate {
+    pub const SPACE: usize =
+        9 + // reward_bps
+        9 + // half_life_seconds
+        10 + // decay_model
+        9 + // quorum
+        9 + // quorum_bps
+        9 + // deposit_locking_period
+        9 + // withdrawal_locking_period
+        9 + // early_exit_penalty_bps
+        33 + // treasury
+        9 + // alpha
+        9 + // heartbeat_seconds
+        9 + // max_update_deviation_bps
+        9 + // max_window_deviation_bps
+        9 + // deviation_window_seconds
+        9 + // max_user_weight
+        9 + // max_user_weight_bps
+        2 + // weighting_mode
+        9 + // min_submit_stake
+        9 + // min_vote_stake
+        9 + // min_submission_interval
+        2 + // access_mode
+        9 + // proposal_voting_period
+        9 + // proposal_execution_delay
+        9 + // proposal_execution_grace
+        9 + // appeal_bond
+        9 + // appeal_voting_period
+        9 + // min_blacklist_duration
+        9 + // dispute_bond
+        9 + // dispute_voting_period
+        9 + // dispute_slash_bps
+        1; // reset_circuit_breaker
+
+    /// Bounds each field the way `initialize` does.
+    fn validate(&self) -> Result<()> {
+        if let Some(reward_bps) = self.reward_bps {
+            require!(reward_bps <= DENOMINATOR, OracleError::InvalidRewardRate);
+        }
+        if let Some(quorum_bps) = self.quorum_bps {
+            require!(quorum_bps <= DENOMINATOR, OracleError::InvalidQuorum);
+        }
+        if let Some(decay_model) = self.decay_model {
+            require!(decay_model.is_valid(), OracleError::InvalidDecayModel);
+        }
+        if let Some(penalty_bps) = self.early_exit_penalty_bps {
+            require!(penalty_bps <= DENOMINATOR, OracleError::InvalidPenaltyRate);
+        }
+        if let Some(slash_bps) = self.dispute_slash_bps {
+            require!(slash_bps <= DENOMINATOR, OracleError::InvalidSlashRate);
+        }
+        if let Some(cap_bps) = self.max_user_weight_bps {
+            require!(cap_bps <= DENOMINATOR, OracleError::InvalidWeightCap);
+        }
+        if let Some(voting_period) = self.proposal_voting_period {
+            // Without a voting period no later proposal could undo this one.
+            require!(voting_period > 0, OracleError::GovernanceDisabled);
+        }
+        Ok(())
+    }
+
+    fn apply(&self, state: &mut Account<OracleState>, now: i64) -> Result<()> {
+        self.validate()?;
+
+        macro_rules! set {
+            ($($field:ident),* $(,)?) => {
+                $(if let Some(value) = self.$field {
+                    state.$field = value;
+                })*
+            };
+        }
+        set!(
+            reward_bps,
+            half_life_seconds,
+            decay_model,
+            quorum,
+            quorum_bps,
+            deposit_locking_period,
+            withdrawal_locking_period,
+            early_exit_penalty_bps,
+            treasury,
+            alpha,
+            heartbeat_seconds,
+            max_update_deviation_bps,
+            max_window_deviation_bps,
+            deviation_window_seconds,
+            max_user_weight,
+            max_user_weight_bps,
+            weighting_mode,
+            min_submit_stake,
+            min_vote_stake,
+            min_submission_interval,
+            access_mode,
+            proposal_voting_period,
+            proposal_execution_delay,
+            proposal_execution_grace,
+            appeal_bond,
+            appeal_voting_period,
+            min_blacklist_duration,
+            dispute_bond,
+            dispute_voting_period,
+            dispute_slash_bps,
+        );
+        require!(
+            state.max_window_deviation_bps == 0 || state.deviation_window_seconds > 0,
+            OracleError::InvalidDeviationWindow
+        );
+        require!(
+            state.stake_kind != StakeKind::NftCollection || state.withdrawal_locking_period == 0,
+            OracleError::UnbondingUnsupported
+        );
+        require!(
+            state.proposal_voting_period == 0 || state.proposal_execution_grace > 0,
+            OracleError::InvalidExecutionGrace
+        );
+        require!(
+            state.dispute_voting_period == 0 || state.dispute_bond > 0,
+            OracleError::InvalidDisputeBond
+        );
+        require!(
+            state.dispute_voting_period == 0 || state.receipt_mint == Pubkey::default(),
+            OracleError::ReceiptsWithDisputes
+        );
+
+        if self.reset_circuit_breaker && state.status == OracleStatus::Halted {
+            state.status = OracleStatus::Active;
+            state.breaker_reset_time = now;
+            emit!(CircuitBreakerReset {
+                oracle: state.key(),
+                timestamp: now,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// How a submission's weight fades with age.
+///
+/// `Linear` and `Step` are evaluated per contribution from `PriceHistory`, so
+/// only the last `MAX_HISTORY_ENTRIES` submissions can count towards the
+/// aggregate; older ones are dropped even if still inside the window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DecayModel {
+    /// Weight halves every `half_life_seconds`; a half-life of 0 disables decay.
+    Exponential,
+    /// Weight falls linearly to zero over `window_seconds`.
+    Linear { window_seconds: u64 },
+    /// Weight counts in full until it is older than `cutoff_seconds`.
+    Step { cutoff_seconds: u64 },
+    /// Weight never decays. Nothing expires, so no activity rewards are paid.
+    None,
+}
+
+/// What stakers lock up for weight. Amounts in `UserState` count lamports on
+/// native oracles and NFTs on collection oracles.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum StakeKind {
+    Token,
+    Native,
+    NftCollection,
+}
+
+/// How a staker's capped token weight maps to submission and voting power.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WeightingMode {
+    /// One token, one unit of weight.
+    Linear,
+    /// Weight grows with the square root of tokens, damping large holders.
+    SquareRoot,
+}
+
+/// Who may call `submit_value`; staking and delegation stay open either way.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AccessMode {
+    /// Any staker meeting the stake minimum.
+    Open,
+    /// Only stakers holding a `SubmitterPermit`.
+    Permissioned,
+}
+
+/// Where a history entry stands in the dispute process.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeOutcome {
+    Undisputed,
+    /// A dispute is open and being voted on.
+    Pending,
+    /// Stakers agreed the value was wrong; the submitter was slashed.
+    Upheld,
+    /// Stakers backed the value; the disputer's bond went to the reward pool.
+    Rejected,
+}
+
+/// Whether consumers should trust `aggregated_value`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OracleStatus {
+    Active,
+    /// No submission within `heartbeat_seconds`; cleared by the next submission.
+    Stale,
+    /// A submission tripped the circuit breaker; submissions are rejected
+    /// until the authority resets it.
+    Halted,
+}
+
+impl WeightingMode {
+    fn apply(self, tokens: u64) -> u64 {
+        match self {
+            WeightingMode::Linear => tokens,
+            WeightingMode::SquareRoot => integer_sqrt(tokens),
+        }
+    }
+}
+
+impl DecayModel {
+    fn is_valid(&self) -> bool {
+        match self {
+            DecayModel::Linear { window_seconds } => *window_seconds > 0,
+            DecayModel::Step { cutoff_seconds } => *cutoff_seconds > 0,
+            DecayModel::Exponential | DecayModel::None => true,
+        }
+    }
+
+    /// Whether contributions expire after a fixed age.
+    fn is_windowed(&self) -> bool {
+        matches!(self, DecayModel::Linear { .. } | DecayModel::Step { .. })
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -1047,6 +5703,8 @@ pub struct ValueSubmitted {
     pub submitted_value: i128,
     pub aggregated_value: i128,
     pub weight: u64,
+    /// Tokens above the per-user cap that were not counted.
+    pub capped_weight: u64,
     pub reward_lamports: u64,
 }
 
@@ -1068,6 +5726,20 @@ pub struct TokenWithdrawn {
     pub amount: u64,
 }
 
+#[event]
+pub struct EarlyWithdrawal {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+}
+
+#[event]
+pub struct WithdrawalRequested {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub available_at: i64,
+}
+
 #[event]
 pub struct Voted {
     pub target: Pubkey,
@@ -1076,12 +5748,188 @@ pub struct Voted {
     pub weight: u64,
 }
 
+#[event]
+pub struct VoteWeightUpdated {
+    pub user: Pubkey,
+    pub weight: u64,
+    pub capped_weight: u64,
+}
+
+#[event]
+pub struct NftDeposited {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct NftWithdrawn {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct SubmitterChanged {
+    pub owner: Pubkey,
+    pub submitter: Pubkey,
+}
+
+#[event]
+pub struct Delegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct Undelegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub available_at: i64,
+}
+
+#[event]
+pub struct DelegationRewardClaimed {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OracleStale {
+    pub oracle: Pubkey,
+    pub last_submission_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub oracle: Pubkey,
+    pub submitter: Pubkey,
+    pub reference_value: i128,
+    pub rejected_value: i128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerReset {
+    pub oracle: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct BlacklistStatusChanged {
     pub target: Pubkey,
     pub is_blacklisted: bool,
 }
 
+#[event]
+pub struct SubmitterPermitGranted {
+    pub oracle: Pubkey,
+    pub submitter: Pubkey,
+    pub via_vote: bool,
+}
+
+#[event]
+pub struct SubmitterPermitRevoked {
+    pub oracle: Pubkey,
+    pub submitter: Pubkey,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub oracle: Pubkey,
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct ProposalVoted {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub oracle: Pubkey,
+    pub proposal: Pubkey,
+    pub id: u64,
+}
+
+#[event]
+pub struct AppealOpened {
+    pub oracle: Pubkey,
+    pub appellant: Pubkey,
+    pub bond: u64,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct AppealVoted {
+    pub appellant: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+#[event]
+pub struct AppealResolved {
+    pub oracle: Pubkey,
+    pub appellant: Pubkey,
+    pub granted: bool,
+    pub support_votes: u64,
+    pub oppose_votes: u64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub oracle: Pubkey,
+    pub disputer: Pubkey,
+    pub submitter: Pubkey,
+    pub record_sequence: u64,
+    pub record_timestamp: i64,
+    pub disputed_value: i128,
+    pub proposed_value: i128,
+    pub bond: u64,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct DisputeVoted {
+    pub dispute: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub oracle: Pubkey,
+    pub disputer: Pubkey,
+    pub submitter: Pubkey,
+    pub record_sequence: u64,
+    pub record_timestamp: i64,
+    pub upheld: bool,
+    pub slashed: u64,
+}
+
+#[event]
+pub struct ReceiptMintCreated {
+    pub oracle: Pubkey,
+    pub receipt_mint: Pubkey,
+}
+
+#[event]
+pub struct ReceiptsFrozenChanged {
+    pub owner: Pubkey,
+    pub account: Pubkey,
+    pub frozen: bool,
+}
+
 #[error_code]
 pub enum OracleError {
     #[msg("Invalid oracle name provided")]
@@ -1116,4 +5964,201 @@ pub enum OracleError {
     TooManyTargets,
     #[msg("Account authority does not match expected value")]
     InvalidAuthority,
+    #[msg("Decay model window must be positive")]
+    InvalidDecayModel,
+    #[msg("Oracle has no heartbeat configured")]
+    HeartbeatDisabled,
+    #[msg("Heartbeat has not been missed yet")]
+    HeartbeatNotMissed,
+    #[msg("Window deviation limit requires a positive window")]
+    InvalidDeviationWindow,
+    #[msg("Oracle is halted by the circuit breaker")]
+    OracleHalted,
+    #[msg("Oracle is not halted")]
+    OracleNotHalted,
+    #[msg("Too many deposits are still locked")]
+    TooManyDepositLots,
+    #[msg("Too many withdrawals are already pending")]
+    TooManyPendingWithdrawals,
+    #[msg("Penalty rate must be less than or equal to denominator")]
+    InvalidPenaltyRate,
+    #[msg("Insufficient deposited tokens")]
+    InsufficientDepositedTokens,
+    #[msg("Treasury account does not match oracle configuration")]
+    InvalidTreasury,
+    #[msg("Vote-escrow locking is not enabled for this oracle")]
+    VeLockDisabled,
+    #[msg("Lock duration exceeds the maximum allowed")]
+    InvalidLockDuration,
+    #[msg("Tokens are locked in vote escrow")]
+    VeLockActive,
+    #[msg("Weight cap exceeds the denominator")]
+    InvalidWeightCap,
+    #[msg("Cannot delegate to yourself")]
+    SelfDelegation,
+    #[msg("Delegate account does not match the target")]
+    InvalidDelegate,
+    #[msg("Commission exceeds the denominator")]
+    InvalidCommission,
+    #[msg("Undelegation has already been requested")]
+    UndelegationPending,
+    #[msg("Delegation is still active")]
+    DelegationActive,
+    #[msg("Undelegation is still within the locking period")]
+    UndelegationLocked,
+    #[msg("Signer is neither the staker nor its designated submitter")]
+    UnauthorizedSubmitter,
+    #[msg("Stake is below the minimum required to submit")]
+    StakeBelowSubmitMinimum,
+    #[msg("Stake is below the minimum required to vote")]
+    StakeBelowVoteMinimum,
+    #[msg("Submission is too soon after the previous one")]
+    SubmissionTooFrequent,
+    #[msg("Weight mint has an unsupported Token-2022 extension")]
+    UnsupportedMintExtension,
+    #[msg("Oracle is not weighted by native SOL")]
+    NotNativeOracle,
+    #[msg("Oracle is not weighted by an NFT collection")]
+    NotNftOracle,
+    #[msg("Oracle is not weighted by a fungible token")]
+    NotTokenOracle,
+    #[msg("NFT is not a verified member of the oracle's collection")]
+    InvalidCollection,
+    #[msg("Receipt mint already exists or stake was deposited before it")]
+    ReceiptMintUnavailable,
+    #[msg("Account is not the oracle's receipt mint")]
+    InvalidReceiptMint,
+    #[msg("Receipt mint and token accounts are required")]
+    ReceiptAccountsMissing,
+    #[msg("Receipt holder is not blacklisted")]
+    ReceiptOwnerNotBlacklisted,
+    #[msg("Receipt holder is blacklisted")]
+    ReceiptOwnerBlacklisted,
+    #[msg("Submitter is not on the oracle's allowlist")]
+    SubmitterNotPermitted,
+    #[msg("Account has not been approved by whitelist vote")]
+    NotWhitelisted,
+    #[msg("Only the authority may revoke this permit")]
+    UnauthorizedRevocation,
+    #[msg("Governance proposals are disabled for this oracle")]
+    GovernanceDisabled,
+    #[msg("Proposal id must equal the oracle's proposal count")]
+    InvalidProposalId,
+    #[msg("Proposal belongs to a different oracle")]
+    InvalidProposal,
+    #[msg("Voting on this proposal has closed")]
+    ProposalVotingClosed,
+    #[msg("Proposal cannot be executed yet")]
+    ProposalNotExecutable,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal did not pass")]
+    ProposalRejected,
+    #[msg("Blacklist appeals are disabled for this oracle")]
+    AppealsDisabled,
+    #[msg("Account is not blacklisted")]
+    NotBlacklisted,
+    #[msg("Minimum blacklist duration has not elapsed")]
+    AppealTooEarly,
+    #[msg("Voting on this appeal has closed")]
+    AppealVotingClosed,
+    #[msg("Voting on this appeal is still open")]
+    AppealVotingOpen,
+    #[msg("Appellant cannot vote on their own appeal")]
+    AppellantCannotVote,
+    #[msg("Quorum share must be less than or equal to denominator")]
+    InvalidQuorum,
+    #[msg("Target has no vote record")]
+    UnknownTarget,
+    #[msg("No stake was held before the vote's snapshot")]
+    NoStakeAtSnapshot,
+    #[msg("Slash rate must be less than or equal to denominator")]
+    InvalidSlashRate,
+    #[msg("Disputes are disabled for this oracle")]
+    DisputesDisabled,
+    #[msg("No recent submission matches the disputed record")]
+    SubmissionNotFound,
+    #[msg("Proposed value must differ from the disputed value")]
+    InvalidDisputeValue,
+    #[msg("History entry has already been disputed")]
+    RecordAlreadyDisputed,
+    #[msg("Dispute belongs to a different oracle")]
+    InvalidDispute,
+    #[msg("Voting on this dispute has closed")]
+    DisputeVotingClosed,
+    #[msg("Voting on this dispute is still open")]
+    DisputeVotingOpen,
+    #[msg("Disputer and submitter cannot vote on their dispute")]
+    DisputePartyCannotVote,
+    #[msg("Stake is locked while a dispute against it is open")]
+    StakeUnderDispute,
+    #[msg("Vault accounts are required to pay slashed stake")]
+    SlashAccountsMissing,
+    #[msg("Token account does not belong to the disputer")]
+    InvalidDisputer,
+    #[msg("Withdrawals on this oracle must go through request_withdrawal")]
+    UnbondingRequired,
+    #[msg("Collection oracles cannot have a withdrawal locking period")]
+    UnbondingUnsupported,
+    #[msg("Early withdrawal is disabled for this oracle")]
+    EarlyExitDisabled,
+    #[msg("Vote-escrow boost exceeds the maximum allowed")]
+    InvalidVeBoost,
+    #[msg("Governance needs a nonzero proposal execution grace period")]
+    InvalidExecutionGrace,
+    #[msg("Proposal execution window has passed")]
+    ProposalExpired,
+    #[msg("Disputes need a nonzero bond")]
+    InvalidDisputeBond,
+    #[msg("Liquid receipts cannot be combined with disputes")]
+    ReceiptsWithDisputes,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deviation_band_is_inclusive() {
+        // 1% of 100_000 is 1_000.
+        assert!(!exceeds_deviation(100_000, 101_000, 1_000));
+        assert!(exceeds_deviation(100_000, 101_001, 1_000));
+        assert!(!exceeds_deviation(100_000, 99_000, 1_000));
+        assert!(exceeds_deviation(100_000, 98_999, 1_000));
+        assert!(!exceeds_deviation(-100_000, -101_000, 1_000));
+        assert!(exceeds_deviation(-100_000, -101_001, 1_000));
+        // Sub-DENOMINATOR references keep their fractional band: 50% of 7 is 3.
+        assert!(!exceeds_deviation(7, 10, 50_000));
+        assert!(exceeds_deviation(7, 11, 50_000));
+    }
+
+    #[test]
+    fn zero_reference_never_trips() {
+        assert!(!exceeds_deviation(0, i128::MAX, 0));
+        assert!(!exceeds_deviation(0, i128::MIN, 1));
+    }
+
+    #[test]
+    fn deviation_handles_i128_extremes() {
+        assert!(exceeds_deviation(i128::MAX, i128::MIN, DENOMINATOR));
+        assert!(exceeds_deviation(i128::MIN, 0, DENOMINATOR - 1));
+        assert!(!exceeds_deviation(i128::MIN, 0, DENOMINATOR));
+        // A 200% band around i128::MIN overflows u128 and admits everything.
+        assert!(!exceeds_deviation(i128::MIN, i128::MAX, 2 * DENOMINATOR));
+        assert!(!exceeds_deviation(i128::MAX, i128::MIN, u64::MAX));
+        assert!(!exceeds_deviation(i128::MAX, i128::MAX, 0));
+        assert!(exceeds_deviation(i128::MAX, i128::MAX - 1, 0));
+    }
+
+    #[test]
+    fn integer_sqrt_floors() {
+        assert_eq!(integer_sqrt(0), 0);
+        assert_eq!(integer_sqrt(1), 1);
+        assert_eq!(integer_sqrt(2), 1);
+        for n in [2u64, 3, 10, 1_000, 65_535, u64::from(u32::MAX)] {
+            assert_eq!(integer_sqrt(n * n), n);
+            assert_eq!(integer_sqrt(n * n - 1), n - 1);
+        }
+        assert_eq!(integer_sqrt(u64::MAX), u64::from(u32::MAX));
+    }
 }
@@ -14,16 +14,16 @@ export type Oracle = {
   },
   "instructions": [
     {
-      "name": "depositTokens",
+      "name": "claimDelegationRewards",
       "discriminator": [
-        176,
-        83,
-        229,
-        18,
-        191,
-        143,
-        176,
-        150
+        113,
+        161,
+        67,
+        53,
+        94,
+        120,
+        78,
+        16
       ],
       "accounts": [
         {
@@ -32,83 +32,246 @@ export type Oracle = {
           "signer": true
         },
         {
-          "name": "weightMint"
+          "name": "oracleState",
+          "writable": true
         },
         {
-          "name": "oracleState",
+          "name": "delegateState",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  114,
-                  97,
-                  99,
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "delegation.delegate",
+                "account": "delegation"
+              }
+            ]
+          }
+        },
+        {
+          "name": "delegation",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
                   108,
-                  101
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
                 ]
               },
               {
                 "kind": "account",
-                "path": "oracle_state.authority",
-                "account": "oracleState"
+                "path": "oracleState"
               },
               {
                 "kind": "account",
-                "path": "oracle_state.weight_mint",
-                "account": "oracleState"
+                "path": "user"
               }
             ]
           }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimSubmitterPermit",
+      "discriminator": [
+        167,
+        32,
+        38,
+        126,
+        129,
+        213,
+        120,
+        126
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
         },
         {
-          "name": "userTokenAccount",
+          "name": "oracleState"
+        },
+        {
+          "name": "submitterPermit",
           "writable": true,
           "pda": {
             "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  117,
+                  98,
+                  109,
+                  105,
+                  116,
+                  116,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
               {
                 "kind": "account",
                 "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "completeUndelegation",
+      "discriminator": [
+        188,
+        134,
+        247,
+        207,
+        129,
+        252,
+        62,
+        44
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
               },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "delegation",
+          "writable": true,
+          "pda": {
+            "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
+                  100,
                   101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
                 ]
               },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "completeWithdrawal",
+      "discriminator": [
+        107,
+        98,
+        134,
+        131,
+        74,
+        120,
+        174,
+        121
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "weightMint"
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
               {
                 "kind": "account",
                 "path": "weightMint"
@@ -163,41 +326,8 @@ export type Oracle = {
                 "path": "oracleState"
               },
               {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
+                "kind": "account",
+                "path": "tokenProgram"
               },
               {
                 "kind": "account",
@@ -269,72 +399,96 @@ export type Oracle = {
           }
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "receiptMint",
+          "docs": [
+            "Required once the oracle has a receipt mint."
+          ],
+          "writable": true,
+          "optional": true
         },
         {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+          "name": "userReceiptAccount",
+          "writable": true,
+          "optional": true
         },
         {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
+          "name": "tokenProgram"
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "fund",
+      "name": "completeWithdrawalNative",
       "discriminator": [
-        218,
-        188,
-        111,
-        221,
-        152,
-        113,
-        174,
-        7
+        223,
+        239,
+        78,
+        2,
+        252,
+        28,
+        2,
+        69
       ],
       "accounts": [
         {
-          "name": "funder",
+          "name": "user",
           "writable": true,
           "signer": true
         },
         {
           "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "nativeVault",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  114,
+                  110,
                   97,
-                  99,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
                   108,
-                  101
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
                 ]
               },
               {
                 "kind": "account",
-                "path": "oracle_state.authority",
-                "account": "oracleState"
+                "path": "oracleState"
               },
               {
                 "kind": "account",
-                "path": "oracle_state.weight_mint",
-                "account": "oracleState"
+                "path": "user"
               }
             ]
           }
@@ -344,230 +498,204 @@ export type Oracle = {
           "address": "11111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "initialize",
+      "name": "createProposal",
       "discriminator": [
-        175,
-        175,
-        109,
-        31,
-        13,
-        152,
-        155,
-        237
+        132,
+        116,
+        68,
+        174,
+        216,
+        160,
+        198,
+        22
       ],
       "accounts": [
         {
-          "name": "payer",
+          "name": "proposer",
           "writable": true,
           "signer": true
         },
         {
-          "name": "authority",
-          "signer": true
-        },
-        {
-          "name": "weightMint"
+          "name": "oracleState",
+          "writable": true
         },
         {
-          "name": "oracleState",
+          "name": "userState",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  114,
-                  97,
-                  99,
-                  108,
-                  101
+                  117,
+                  115,
+                  101,
+                  114
                 ]
               },
               {
                 "kind": "account",
-                "path": "authority"
+                "path": "oracleState"
               },
               {
                 "kind": "account",
-                "path": "weightMint"
+                "path": "proposer"
               }
             ]
           }
         },
         {
-          "name": "oracleVault",
+          "name": "proposal",
           "writable": true,
           "pda": {
             "seeds": [
-              {
-                "kind": "account",
-                "path": "oracleState"
-              },
               {
                 "kind": "const",
                 "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
+                  112,
+                  114,
+                  111,
+                  112,
+                  111,
+                  115,
+                  97,
+                  108
                 ]
               },
               {
                 "kind": "account",
-                "path": "weightMint"
+                "path": "oracleState"
+              },
+              {
+                "kind": "arg",
+                "path": "proposalId"
               }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
+            ]
           }
         },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "params",
+          "name": "proposalId",
+          "type": "u64"
+        },
+        {
+          "name": "update",
           "type": {
             "defined": {
-              "name": "initializeOracleParams"
+              "name": "parameterUpdate"
             }
           }
         }
       ]
     },
     {
-      "name": "submitValue",
+      "name": "createReceiptMint",
       "discriminator": [
-        200,
-        19,
-        205,
-        48,
-        129,
-        237,
+        80,
+        60,
+        150,
+        99,
+        245,
+        244,
         209,
-        223
+        133
       ],
       "accounts": [
         {
-          "name": "user",
+          "name": "payer",
           "writable": true,
           "signer": true
         },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "oracleState"
+          ]
+        },
         {
           "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "weightMint",
+          "relations": [
+            "oracleState"
+          ]
+        },
+        {
+          "name": "receiptMint",
+          "docs": [
+            "Frozen by the oracle for blacklisted holders, so it must never be",
+            "handed to another authority."
+          ],
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
                   114,
-                  97,
+                  101,
                   99,
-                  108,
-                  101
+                  101,
+                  105,
+                  112,
+                  116,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "oracle_state.authority",
-                "account": "oracleState"
-              },
-              {
-                "kind": "account",
-                "path": "oracle_state.weight_mint",
-                "account": "oracleState"
+                "path": "oracleState"
               }
             ]
           }
         },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "delegate",
+      "discriminator": [
+        90,
+        147,
+        75,
+        178,
+        85,
+        88,
+        4,
+        137
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
         {
           "name": "userState",
           "writable": true,
@@ -592,74 +720,50 @@ export type Oracle = {
               }
             ]
           }
-        }
-      ],
-      "args": [
-        {
-          "name": "newValue",
-          "type": "i128"
-        }
-      ]
-    },
-    {
-      "name": "updateUserVoteWeights",
-      "discriminator": [
-        145,
-        4,
-        15,
-        172,
-        0,
-        39,
-        22,
-        7
-      ],
-      "accounts": [
-        {
-          "name": "user",
-          "writable": true,
-          "signer": true
         },
         {
-          "name": "oracleState",
+          "name": "delegateState",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  114,
-                  97,
-                  99,
-                  108,
-                  101
+                  117,
+                  115,
+                  101,
+                  114
                 ]
               },
               {
                 "kind": "account",
-                "path": "oracle_state.authority",
-                "account": "oracleState"
+                "path": "oracleState"
               },
               {
-                "kind": "account",
-                "path": "oracle_state.weight_mint",
-                "account": "oracleState"
+                "kind": "arg",
+                "path": "to"
               }
             ]
           }
         },
         {
-          "name": "userState",
+          "name": "delegation",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  115,
+                  100,
                   101,
-                  114
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
                 ]
               },
               {
@@ -672,21 +776,30 @@ export type Oracle = {
               }
             ]
           }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "to",
+          "type": "pubkey"
+        }
+      ]
     },
     {
-      "name": "voteBlacklist",
+      "name": "depositNative",
       "discriminator": [
-        50,
-        191,
-        156,
-        171,
-        117,
-        193,
-        217,
-        166
+        13,
+        158,
+        13,
+        223,
+        95,
+        213,
+        28,
+        6
       ],
       "accounts": [
         {
@@ -696,29 +809,33 @@ export type Oracle = {
         },
         {
           "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "nativeVault",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  114,
+                  110,
                   97,
-                  99,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
                   108,
-                  101
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "oracle_state.authority",
-                "account": "oracleState"
-              },
-              {
-                "kind": "account",
-                "path": "oracle_state.weight_mint",
-                "account": "oracleState"
+                "path": "oracleState"
               }
             ]
           }
@@ -747,26 +864,30 @@ export type Oracle = {
               }
             ]
           }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "target",
-          "type": "pubkey"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "voteWhitelist",
+      "name": "depositNativeLocked",
       "discriminator": [
+        59,
         143,
-        5,
-        118,
-        62,
-        182,
-        242,
-        171,
-        94
+        3,
+        229,
+        67,
+        191,
+        6,
+        62
       ],
       "accounts": [
         {
@@ -776,29 +897,33 @@ export type Oracle = {
         },
         {
           "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "nativeVault",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  114,
+                  110,
                   97,
-                  99,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
                   108,
-                  101
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "oracle_state.authority",
-                "account": "oracleState"
-              },
-              {
-                "kind": "account",
-                "path": "oracle_state.weight_mint",
-                "account": "oracleState"
+                "path": "oracleState"
               }
             ]
           }
@@ -827,26 +952,34 @@ export type Oracle = {
               }
             ]
           }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "target",
-          "type": "pubkey"
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "lockSeconds",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "withdrawTokens",
+      "name": "depositNft",
       "discriminator": [
-        2,
-        4,
-        225,
-        61,
-        19,
-        182,
-        106,
-        170
+        93,
+        226,
+        132,
+        166,
+        141,
+        9,
+        48,
+        101
       ],
       "accounts": [
         {
@@ -855,83 +988,304 @@ export type Oracle = {
           "signer": true
         },
         {
-          "name": "weightMint"
+          "name": "oracleState",
+          "writable": true
         },
         {
-          "name": "oracleState",
-          "writable": true,
+          "name": "nftMint"
+        },
+        {
+          "name": "nftMetadata",
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  114,
+                  109,
+                  101,
+                  116,
                   97,
-                  99,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "const",
+                "value": [
+                  11,
+                  112,
+                  101,
+                  177,
+                  227,
+                  209,
+                  124,
+                  69,
+                  56,
+                  157,
+                  82,
+                  127,
+                  107,
+                  4,
+                  195,
+                  205,
+                  88,
+                  184,
                   108,
-                  101
+                  115,
+                  26,
+                  160,
+                  253,
+                  181,
+                  73,
+                  182,
+                  209,
+                  188,
+                  3,
+                  248,
+                  41,
+                  70
                 ]
               },
               {
                 "kind": "account",
-                "path": "oracle_state.authority",
-                "account": "oracleState"
+                "path": "nftMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                11,
+                112,
+                101,
+                177,
+                227,
+                209,
+                124,
+                69,
+                56,
+                157,
+                82,
+                127,
+                107,
+                4,
+                195,
+                205,
+                88,
+                184,
+                108,
+                115,
+                26,
+                160,
+                253,
+                181,
+                73,
+                182,
+                209,
+                188,
+                3,
+                248,
+                41,
+                70
+              ]
+            }
+          }
+        },
+        {
+          "name": "userNftAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
               },
               {
                 "kind": "account",
-                "path": "oracle_state.weight_mint",
-                "account": "oracleState"
+                "path": "nftMint"
               }
-            ]
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
           }
         },
         {
-          "name": "userTokenAccount",
+          "name": "nftEscrow",
+          "docs": [
+            "Escrow seeded by depositor and mint, so only the depositor can",
+            "withdraw the NFT."
+          ],
           "writable": true,
           "pda": {
             "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  102,
+                  116,
+                  95,
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
               {
                 "kind": "account",
                 "path": "user"
               },
+              {
+                "kind": "account",
+                "path": "nftMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nftLock",
+          "writable": true,
+          "pda": {
+            "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
+                  110,
+                  102,
+                  116,
                   95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
+                  108,
+                  111,
+                  99,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "nftEscrow"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
                 ]
               },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "depositTokens",
+      "discriminator": [
+        176,
+        83,
+        229,
+        18,
+        191,
+        143,
+        176,
+        150
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "weightMint"
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
               {
                 "kind": "account",
                 "path": "weightMint"
@@ -986,41 +1340,8 @@ export type Oracle = {
                 "path": "oracleState"
               },
               {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
+                "kind": "account",
+                "path": "tokenProgram"
               },
               {
                 "kind": "account",
@@ -1092,8 +1413,32 @@ export type Oracle = {
           }
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "receiptMint",
+          "docs": [
+            "Required once the oracle has a receipt mint."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userReceiptAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
         }
       ],
       "args": [
@@ -1102,383 +1447,5904 @@ export type Oracle = {
           "type": "u64"
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "oracleState",
+      "name": "depositTokensLocked",
       "discriminator": [
-        97,
-        156,
-        157,
-        189,
-        194,
-        73,
-        8,
-        15
+        16,
+        209,
+        99,
+        132,
+        43,
+        102,
+        111,
+        132
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "weightMint"
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "weightMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "oracleVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "weightMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "receiptMint",
+          "docs": [
+            "Required once the oracle has a receipt mint."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userReceiptAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "lockSeconds",
+          "type": "u64"
+        }
       ]
     },
     {
-      "name": "userState",
+      "name": "executeProposal",
       "discriminator": [
-        72,
-        177,
-        85,
-        249,
-        76,
-        167,
         186,
-        126
-      ]
-    }
-  ],
-  "events": [
+        60,
+        116,
+        133,
+        108,
+        128,
+        111,
+        28
+      ],
+      "accounts": [
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "proposal",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
     {
-      "name": "blacklistStatusChanged",
+      "name": "freezeReceipts",
       "discriminator": [
-        199,
         100,
-        107,
-        197,
-        207,
-        115,
-        231,
-        166
-      ]
+        145,
+        167,
+        106,
+        73,
+        187,
+        3,
+        1
+      ],
+      "accounts": [
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "receiptMint"
+        },
+        {
+          "name": "receiptAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": []
     },
     {
-      "name": "funded",
+      "name": "fund",
       "discriminator": [
-        67,
-        84,
-        56,
-        88,
-        192,
-        12,
-        201,
-        177
-      ]
-    },
-    {
-      "name": "tokenDeposited",
-      "discriminator": [
-        104,
-        7,
-        18,
-        187,
-        94,
-        141,
-        251,
-        120
+        218,
+        188,
+        111,
+        221,
+        152,
+        113,
+        174,
+        7
+      ],
+      "accounts": [
+        {
+          "name": "funder",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
       ]
     },
     {
-      "name": "tokenWithdrawn",
+      "name": "grantSubmitter",
       "discriminator": [
-        35,
-        57,
-        130,
-        51,
-        154,
-        247,
-        155,
-        142
+        209,
+        240,
+        255,
+        28,
+        129,
+        196,
+        109,
+        235
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "oracleState"
+          ]
+        },
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "submitterPermit",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  117,
+                  98,
+                  109,
+                  105,
+                  116,
+                  116,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "arg",
+                "path": "submitter"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "submitter",
+          "type": "pubkey"
+        }
       ]
     },
     {
-      "name": "valueSubmitted",
+      "name": "initialize",
       "discriminator": [
-        171,
-        199,
-        35,
-        217,
-        159,
-        103,
-        239,
-        220
+        175,
+        175,
+        109,
+        31,
+        13,
+        152,
+        155,
+        237
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "weightMint"
+        },
+        {
+          "name": "oracleState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  97,
+                  99,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              },
+              {
+                "kind": "account",
+                "path": "weightMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "priceHistory",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "oracleVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "weightMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": {
+              "name": "initializeOracleParams"
+            }
+          }
+        }
       ]
     },
     {
-      "name": "voted",
+      "name": "initializeNative",
+      "docs": [
+        "Creates an oracle weighted by lamports staked in a vault PDA rather",
+        "than by an SPL token."
+      ],
       "discriminator": [
-        189,
-        74,
-        101,
+        58,
+        79,
+        79,
+        22,
         127,
-        109,
-        214,
-        95,
-        130
+        213,
+        117,
+        193
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  97,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  111,
+                  114,
+                  97,
+                  99,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "priceHistory",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nativeVault",
+          "docs": [
+            "Holds staked lamports; seeded apart from the oracle so stake never",
+            "mixes with the reward pool."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  97,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": {
+              "name": "initializeOracleParams"
+            }
+          }
+        }
       ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "invalidName",
-      "msg": "Invalid oracle name provided"
-    },
-    {
-      "code": 6001,
-      "name": "invalidDescription",
-      "msg": "Invalid oracle description provided"
-    },
-    {
-      "code": 6002,
-      "name": "invalidRewardRate",
-      "msg": "Reward rate must be less than or equal to denominator"
-    },
-    {
-      "code": 6003,
-      "name": "invalidPda",
-      "msg": "Provided account does not match derived PDA"
-    },
-    {
-      "code": 6004,
-      "name": "amountMustBePositive",
-      "msg": "Amount must be positive"
-    },
-    {
-      "code": 6005,
-      "name": "mathOverflow",
-      "msg": "Math overflow"
     },
     {
-      "code": 6006,
-      "name": "mathUnderflow",
-      "msg": "Math underflow"
+      "name": "initializeNft",
+      "docs": [
+        "Creates an oracle where every verified NFT of `collection_mint`",
+        "deposited by a user counts as one unit of weight."
+      ],
+      "discriminator": [
+        200,
+        16,
+        218,
+        53,
+        27,
+        164,
+        153,
+        152
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "collectionMint"
+        },
+        {
+          "name": "oracleState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  97,
+                  99,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              },
+              {
+                "kind": "account",
+                "path": "collectionMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "priceHistory",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": {
+              "name": "initializeOracleParams"
+            }
+          }
+        }
+      ]
     },
     {
-      "code": 6007,
-      "name": "withdrawalLocked",
-      "msg": "Withdrawal still locked"
+      "name": "openAppeal",
+      "discriminator": [
+        112,
+        99,
+        100,
+        165,
+        154,
+        93,
+        128,
+        191
+      ],
+      "accounts": [
+        {
+          "name": "appellant",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "appeal",
+          "docs": [
+            "One open appeal per target; holds the bond until resolution."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  112,
+                  112,
+                  101,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "appellant"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6008,
-      "name": "insufficientUnlockedTokens",
-      "msg": "Insufficient unlocked tokens"
-    },
+      "name": "openDispute",
+      "discriminator": [
+        137,
+        25,
+        99,
+        119,
+        23,
+        223,
+        161,
+        42
+      ],
+      "accounts": [
+        {
+          "name": "disputer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "submitterState",
+          "docs": [
+            "The staker whose submission is disputed."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "submitter_state.owner",
+                "account": "userState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "priceHistory",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "dispute",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  105,
+                  115,
+                  112,
+                  117,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "submitter_state.owner",
+                "account": "userState"
+              },
+              {
+                "kind": "arg",
+                "path": "recordSequence"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "recordSequence",
+          "type": "u64"
+        },
+        {
+          "name": "proposedValue",
+          "type": "i128"
+        }
+      ]
+    },
     {
-      "code": 6009,
-      "name": "accountBlacklisted",
-      "msg": "Account is blacklisted"
+      "name": "poke",
+      "discriminator": [
+        46,
+        24,
+        16,
+        107,
+        212,
+        9,
+        17,
+        5
+      ],
+      "accounts": [
+        {
+          "name": "oracleState",
+          "writable": true
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6010,
-      "name": "noUnlockedTokens",
-      "msg": "No unlocked tokens available"
+      "name": "refreshTarget",
+      "docs": [
+        "Re-applies the blacklist rule to `target`, e.g. after stake moved a",
+        "percentage quorum."
+      ],
+      "discriminator": [
+        216,
+        233,
+        132,
+        134,
+        172,
+        84,
+        246,
+        60
+      ],
+      "accounts": [
+        {
+          "name": "oracleState",
+          "writable": true
+        }
+      ],
+      "args": [
+        {
+          "name": "target",
+          "type": "pubkey"
+        }
+      ]
     },
     {
-      "code": 6011,
-      "name": "zeroWeightAfterUpdate",
-      "msg": "Weight updates resulted in zero total weight"
+      "name": "requestWithdrawal",
+      "discriminator": [
+        251,
+        85,
+        121,
+        205,
+        56,
+        201,
+        12,
+        177
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "receiptMint",
+          "docs": [
+            "Required once the oracle has a receipt mint."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userReceiptAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "optional": true
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "resetCircuitBreaker",
+      "discriminator": [
+        225,
+        48,
+        84,
+        136,
+        90,
+        146,
+        26,
+        149
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true,
+          "relations": [
+            "oracleState"
+          ]
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resolveAppeal",
+      "discriminator": [
+        225,
+        133,
+        69,
+        13,
+        240,
+        143,
+        68,
+        170
+      ],
+      "accounts": [
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "appeal",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  112,
+                  112,
+                  101,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "appeal.appellant",
+                "account": "appeal"
+              }
+            ]
+          }
+        },
+        {
+          "name": "appellant",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resolveDispute",
+      "discriminator": [
+        231,
+        6,
+        202,
+        6,
+        96,
+        103,
+        12,
+        230
+      ],
+      "accounts": [
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "dispute",
+          "writable": true
+        },
+        {
+          "name": "disputer",
+          "writable": true
+        },
+        {
+          "name": "submitterState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "dispute.submitter",
+                "account": "dispute"
+              }
+            ]
+          }
+        },
+        {
+          "name": "priceHistory",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "weightMint",
+          "docs": [
+            "Token oracles pay slashed stake from the vault with these."
+          ],
+          "optional": true
+        },
+        {
+          "name": "oracleVault",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "disputerTokenAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "optional": true
+        },
+        {
+          "name": "nativeVault",
+          "docs": [
+            "Native oracles pay slashed stake from here."
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  97,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "submitterDelegation",
+          "docs": [
+            "Required when the submitter has stake delegated away, so it can be",
+            "slashed too."
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "dispute.submitter",
+                "account": "dispute"
+              }
+            ]
+          }
+        },
+        {
+          "name": "delegateState",
+          "docs": [
+            "The delegate's state, while that delegation is still active."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "revokeSubmitter",
+      "discriminator": [
+        107,
+        112,
+        48,
+        28,
+        132,
+        184,
+        145,
+        251
+      ],
+      "accounts": [
+        {
+          "name": "caller",
+          "signer": true
+        },
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "submitterPermit",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  117,
+                  98,
+                  109,
+                  105,
+                  116,
+                  116,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "submitter_permit.submitter",
+                "account": "submitterPermit"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "relations": [
+            "submitterPermit"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setCommission",
+      "discriminator": [
+        193,
+        212,
+        22,
+        184,
+        185,
+        232,
+        22,
+        187
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "commissionBps",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setSubmitter",
+      "docs": [
+        "Lets `hot_key` submit values and vote for the staker. Pass the default",
+        "pubkey to revoke. Deposits and withdrawals still need the owner."
+      ],
+      "discriminator": [
+        165,
+        82,
+        62,
+        88,
+        103,
+        210,
+        119,
+        178
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "hotKey",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "submitValue",
+      "discriminator": [
+        200,
+        19,
+        205,
+        48,
+        129,
+        237,
+        209,
+        223
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userState",
+          "docs": [
+            "The staker's account; the signer is its owner or designated submitter."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user_state.owner",
+                "account": "userState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "docs": [
+            "Receives the activity reward, so a hot key can submit without earning."
+          ],
+          "writable": true
+        },
+        {
+          "name": "priceHistory",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "submitterPermit",
+          "docs": [
+            "Required on permissioned oracles."
+          ],
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  117,
+                  98,
+                  109,
+                  105,
+                  116,
+                  116,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user_state.owner",
+                "account": "userState"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "newValue",
+          "type": "i128"
+        }
+      ]
+    },
+    {
+      "name": "thawReceipts",
+      "discriminator": [
+        123,
+        255,
+        108,
+        211,
+        253,
+        103,
+        176,
+        116
+      ],
+      "accounts": [
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "receiptMint"
+        },
+        {
+          "name": "receiptAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "undelegate",
+      "discriminator": [
+        131,
+        148,
+        180,
+        198,
+        91,
+        104,
+        42,
+        238
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "delegateState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "delegation.delegate",
+                "account": "delegation"
+              }
+            ]
+          }
+        },
+        {
+          "name": "delegation",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateUserVoteWeights",
+      "docs": [
+        "Re-syncs a staker's vote weight. Anyone may call it, so weight that",
+        "changed without an instruction from the staker, such as matured lots,",
+        "a decaying vote-escrow boost or a moved weight cap, reaches",
+        "`total_vote_weight` and later snapshots."
+      ],
+      "discriminator": [
+        145,
+        4,
+        15,
+        172,
+        0,
+        39,
+        22,
+        7
+      ],
+      "accounts": [
+        {
+          "name": "caller",
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user_state.owner",
+                "account": "userState"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "voteAppeal",
+      "discriminator": [
+        218,
+        129,
+        121,
+        145,
+        140,
+        172,
+        135,
+        52
+      ],
+      "accounts": [
+        {
+          "name": "voter",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "userState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "voter"
+              }
+            ]
+          }
+        },
+        {
+          "name": "appeal",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  112,
+                  112,
+                  101,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "appeal.appellant",
+                "account": "appeal"
+              }
+            ]
+          }
+        },
+        {
+          "name": "appealVote",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  112,
+                  112,
+                  101,
+                  97,
+                  108,
+                  95,
+                  118,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "appeal"
+              },
+              {
+                "kind": "account",
+                "path": "voter"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "support",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "voteBlacklist",
+      "discriminator": [
+        50,
+        191,
+        156,
+        171,
+        117,
+        193,
+        217,
+        166
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user_state.owner",
+                "account": "userState"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "target",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "voteDispute",
+      "discriminator": [
+        23,
+        190,
+        211,
+        170,
+        65,
+        223,
+        4,
+        243
+      ],
+      "accounts": [
+        {
+          "name": "voter",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "userState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "voter"
+              }
+            ]
+          }
+        },
+        {
+          "name": "dispute",
+          "writable": true
+        },
+        {
+          "name": "disputeVote",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  105,
+                  115,
+                  112,
+                  117,
+                  116,
+                  101,
+                  95,
+                  118,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "dispute"
+              },
+              {
+                "kind": "account",
+                "path": "voter"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "support",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "voteProposal",
+      "discriminator": [
+        247,
+        104,
+        114,
+        240,
+        237,
+        41,
+        200,
+        36
+      ],
+      "accounts": [
+        {
+          "name": "voter",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState"
+        },
+        {
+          "name": "userState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "voter"
+              }
+            ]
+          }
+        },
+        {
+          "name": "proposal",
+          "writable": true
+        },
+        {
+          "name": "proposalVote",
+          "docs": [
+            "Exists once per voter, so a second vote fails on `init`."
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  112,
+                  111,
+                  115,
+                  97,
+                  108,
+                  95,
+                  118,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "proposal"
+              },
+              {
+                "kind": "account",
+                "path": "voter"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "support",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "voteWhitelist",
+      "discriminator": [
+        143,
+        5,
+        118,
+        62,
+        182,
+        242,
+        171,
+        94
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user_state.owner",
+                "account": "userState"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "target",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "withdrawEarly",
+      "discriminator": [
+        173,
+        230,
+        246,
+        12,
+        114,
+        228,
+        15,
+        106
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "weightMint"
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "weightMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "oracleVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "weightMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "treasuryTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "receiptMint",
+          "docs": [
+            "Required once the oracle has a receipt mint."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userReceiptAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawEarlyNative",
+      "discriminator": [
+        246,
+        134,
+        49,
+        207,
+        174,
+        246,
+        50,
+        66
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "nativeVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  97,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawNative",
+      "discriminator": [
+        113,
+        227,
+        26,
+        32,
+        53,
+        66,
+        90,
+        250
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "nativeVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  97,
+                  116,
+                  105,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawNft",
+      "discriminator": [
+        142,
+        181,
+        191,
+        149,
+        82,
+        175,
+        216,
+        100
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "nftMint"
+        },
+        {
+          "name": "userNftAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "nftMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "nftEscrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  102,
+                  116,
+                  95,
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "nftMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nftLock",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  102,
+                  116,
+                  95,
+                  108,
+                  111,
+                  99,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "nftEscrow"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawTokens",
+      "discriminator": [
+        2,
+        4,
+        225,
+        61,
+        19,
+        182,
+        106,
+        170
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "weightMint"
+        },
+        {
+          "name": "oracleState",
+          "writable": true
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "weightMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "oracleVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "weightMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "oracleState"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "receiptMint",
+          "docs": [
+            "Required once the oracle has a receipt mint."
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userReceiptAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "appeal",
+      "discriminator": [
+        155,
+        196,
+        80,
+        143,
+        64,
+        220,
+        198,
+        177
+      ]
+    },
+    {
+      "name": "appealVote",
+      "discriminator": [
+        227,
+        22,
+        33,
+        205,
+        60,
+        98,
+        180,
+        89
+      ]
+    },
+    {
+      "name": "delegation",
+      "discriminator": [
+        237,
+        90,
+        140,
+        159,
+        124,
+        255,
+        243,
+        80
+      ]
+    },
+    {
+      "name": "dispute",
+      "discriminator": [
+        36,
+        49,
+        241,
+        67,
+        40,
+        36,
+        241,
+        74
+      ]
+    },
+    {
+      "name": "disputeVote",
+      "discriminator": [
+        166,
+        202,
+        140,
+        76,
+        65,
+        35,
+        254,
+        149
+      ]
+    },
+    {
+      "name": "nftLock",
+      "discriminator": [
+        24,
+        21,
+        40,
+        53,
+        7,
+        145,
+        75,
+        111
+      ]
+    },
+    {
+      "name": "oracleState",
+      "discriminator": [
+        97,
+        156,
+        157,
+        189,
+        194,
+        73,
+        8,
+        15
+      ]
+    },
+    {
+      "name": "priceHistory",
+      "discriminator": [
+        38,
+        241,
+        40,
+        19,
+        42,
+        228,
+        93,
+        152
+      ]
+    },
+    {
+      "name": "proposal",
+      "discriminator": [
+        26,
+        94,
+        189,
+        187,
+        116,
+        136,
+        53,
+        33
+      ]
+    },
+    {
+      "name": "proposalVote",
+      "discriminator": [
+        65,
+        232,
+        198,
+        107,
+        71,
+        106,
+        252,
+        40
+      ]
+    },
+    {
+      "name": "submitterPermit",
+      "discriminator": [
+        237,
+        204,
+        74,
+        73,
+        172,
+        229,
+        68,
+        139
+      ]
+    },
+    {
+      "name": "userState",
+      "discriminator": [
+        72,
+        177,
+        85,
+        249,
+        76,
+        167,
+        186,
+        126
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "appealOpened",
+      "discriminator": [
+        141,
+        237,
+        105,
+        144,
+        34,
+        130,
+        228,
+        161
+      ]
+    },
+    {
+      "name": "appealResolved",
+      "discriminator": [
+        130,
+        12,
+        137,
+        191,
+        189,
+        217,
+        26,
+        189
+      ]
+    },
+    {
+      "name": "appealVoted",
+      "discriminator": [
+        240,
+        24,
+        57,
+        164,
+        72,
+        153,
+        238,
+        91
+      ]
+    },
+    {
+      "name": "blacklistStatusChanged",
+      "discriminator": [
+        199,
+        100,
+        107,
+        197,
+        207,
+        115,
+        231,
+        166
+      ]
+    },
+    {
+      "name": "circuitBreakerReset",
+      "discriminator": [
+        215,
+        141,
+        199,
+        11,
+        71,
+        140,
+        36,
+        87
+      ]
+    },
+    {
+      "name": "circuitBreakerTripped",
+      "discriminator": [
+        188,
+        9,
+        111,
+        118,
+        136,
+        206,
+        199,
+        65
+      ]
+    },
+    {
+      "name": "delegated",
+      "discriminator": [
+        52,
+        201,
+        7,
+        194,
+        24,
+        79,
+        100,
+        159
+      ]
+    },
+    {
+      "name": "delegationRewardClaimed",
+      "discriminator": [
+        242,
+        248,
+        36,
+        16,
+        162,
+        44,
+        196,
+        46
+      ]
+    },
+    {
+      "name": "disputeOpened",
+      "discriminator": [
+        239,
+        222,
+        102,
+        235,
+        193,
+        85,
+        1,
+        214
+      ]
+    },
+    {
+      "name": "disputeResolved",
+      "discriminator": [
+        121,
+        64,
+        249,
+        153,
+        139,
+        128,
+        236,
+        187
+      ]
+    },
+    {
+      "name": "disputeVoted",
+      "discriminator": [
+        246,
+        199,
+        201,
+        173,
+        194,
+        194,
+        97,
+        73
+      ]
+    },
+    {
+      "name": "earlyWithdrawal",
+      "discriminator": [
+        85,
+        73,
+        106,
+        98,
+        36,
+        86,
+        103,
+        133
+      ]
+    },
+    {
+      "name": "funded",
+      "discriminator": [
+        67,
+        84,
+        56,
+        88,
+        192,
+        12,
+        201,
+        177
+      ]
+    },
+    {
+      "name": "nftDeposited",
+      "discriminator": [
+        5,
+        219,
+        175,
+        64,
+        104,
+        214,
+        139,
+        79
+      ]
+    },
+    {
+      "name": "nftWithdrawn",
+      "discriminator": [
+        114,
+        42,
+        189,
+        19,
+        12,
+        216,
+        81,
+        185
+      ]
+    },
+    {
+      "name": "oracleStale",
+      "discriminator": [
+        251,
+        199,
+        174,
+        69,
+        166,
+        188,
+        187,
+        138
+      ]
+    },
+    {
+      "name": "proposalCreated",
+      "discriminator": [
+        186,
+        8,
+        160,
+        108,
+        81,
+        13,
+        51,
+        206
+      ]
+    },
+    {
+      "name": "proposalExecuted",
+      "discriminator": [
+        92,
+        213,
+        189,
+        201,
+        101,
+        83,
+        111,
+        83
+      ]
+    },
+    {
+      "name": "proposalVoted",
+      "discriminator": [
+        237,
+        153,
+        165,
+        162,
+        162,
+        46,
+        243,
+        224
+      ]
+    },
+    {
+      "name": "receiptMintCreated",
+      "discriminator": [
+        244,
+        52,
+        95,
+        123,
+        220,
+        254,
+        57,
+        145
+      ]
+    },
+    {
+      "name": "receiptsFrozenChanged",
+      "discriminator": [
+        239,
+        196,
+        199,
+        252,
+        170,
+        134,
+        208,
+        144
+      ]
+    },
+    {
+      "name": "submitterChanged",
+      "discriminator": [
+        54,
+        124,
+        250,
+        208,
+        247,
+        121,
+        101,
+        116
+      ]
+    },
+    {
+      "name": "submitterPermitGranted",
+      "discriminator": [
+        218,
+        239,
+        217,
+        219,
+        54,
+        131,
+        214,
+        4
+      ]
+    },
+    {
+      "name": "submitterPermitRevoked",
+      "discriminator": [
+        62,
+        254,
+        27,
+        92,
+        228,
+        84,
+        20,
+        236
+      ]
+    },
+    {
+      "name": "tokenDeposited",
+      "discriminator": [
+        104,
+        7,
+        18,
+        187,
+        94,
+        141,
+        251,
+        120
+      ]
+    },
+    {
+      "name": "tokenWithdrawn",
+      "discriminator": [
+        35,
+        57,
+        130,
+        51,
+        154,
+        247,
+        155,
+        142
+      ]
+    },
+    {
+      "name": "undelegated",
+      "discriminator": [
+        207,
+        179,
+        100,
+        125,
+        238,
+        150,
+        136,
+        31
+      ]
+    },
+    {
+      "name": "valueSubmitted",
+      "discriminator": [
+        171,
+        199,
+        35,
+        217,
+        159,
+        103,
+        239,
+        220
+      ]
+    },
+    {
+      "name": "voteWeightUpdated",
+      "discriminator": [
+        127,
+        148,
+        14,
+        247,
+        20,
+        230,
+        137,
+        125
+      ]
+    },
+    {
+      "name": "voted",
+      "discriminator": [
+        189,
+        74,
+        101,
+        127,
+        109,
+        214,
+        95,
+        130
+      ]
+    },
+    {
+      "name": "withdrawalRequested",
+      "discriminator": [
+        75,
+        207,
+        21,
+        12,
+        160,
+        102,
+        150,
+        55
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidName",
+      "msg": "Invalid oracle name provided"
+    },
+    {
+      "code": 6001,
+      "name": "invalidDescription",
+      "msg": "Invalid oracle description provided"
+    },
+    {
+      "code": 6002,
+      "name": "invalidRewardRate",
+      "msg": "Reward rate must be less than or equal to denominator"
+    },
+    {
+      "code": 6003,
+      "name": "invalidPda",
+      "msg": "Provided account does not match derived PDA"
+    },
+    {
+      "code": 6004,
+      "name": "amountMustBePositive",
+      "msg": "Amount must be positive"
+    },
+    {
+      "code": 6005,
+      "name": "mathOverflow",
+      "msg": "Math overflow"
+    },
+    {
+      "code": 6006,
+      "name": "mathUnderflow",
+      "msg": "Math underflow"
+    },
+    {
+      "code": 6007,
+      "name": "withdrawalLocked",
+      "msg": "Withdrawal still locked"
+    },
+    {
+      "code": 6008,
+      "name": "insufficientUnlockedTokens",
+      "msg": "Insufficient unlocked tokens"
+    },
+    {
+      "code": 6009,
+      "name": "accountBlacklisted",
+      "msg": "Account is blacklisted"
+    },
+    {
+      "code": 6010,
+      "name": "noUnlockedTokens",
+      "msg": "No unlocked tokens available"
+    },
+    {
+      "code": 6011,
+      "name": "zeroWeightAfterUpdate",
+      "msg": "Weight updates resulted in zero total weight"
+    },
+    {
+      "code": 6012,
+      "name": "alreadyVoted",
+      "msg": "User has already voted on this target"
+    },
+    {
+      "code": 6013,
+      "name": "tooManyVotes",
+      "msg": "Exceeded vote capacity for user"
+    },
+    {
+      "code": 6014,
+      "name": "tooManyTargets",
+      "msg": "Exceeded governance target capacity"
+    },
+    {
+      "code": 6015,
+      "name": "invalidAuthority",
+      "msg": "Account authority does not match expected value"
+    },
+    {
+      "code": 6016,
+      "name": "invalidDecayModel",
+      "msg": "Decay model window must be positive"
+    },
+    {
+      "code": 6017,
+      "name": "heartbeatDisabled",
+      "msg": "Oracle has no heartbeat configured"
+    },
+    {
+      "code": 6018,
+      "name": "heartbeatNotMissed",
+      "msg": "Heartbeat has not been missed yet"
+    },
+    {
+      "code": 6019,
+      "name": "invalidDeviationWindow",
+      "msg": "Window deviation limit requires a positive window"
+    },
+    {
+      "code": 6020,
+      "name": "oracleHalted",
+      "msg": "Oracle is halted by the circuit breaker"
+    },
+    {
+      "code": 6021,
+      "name": "oracleNotHalted",
+      "msg": "Oracle is not halted"
+    },
+    {
+      "code": 6022,
+      "name": "tooManyDepositLots",
+      "msg": "Too many deposits are still locked"
+    },
+    {
+      "code": 6023,
+      "name": "tooManyPendingWithdrawals",
+      "msg": "Too many withdrawals are already pending"
+    },
+    {
+      "code": 6024,
+      "name": "invalidPenaltyRate",
+      "msg": "Penalty rate must be less than or equal to denominator"
+    },
+    {
+      "code": 6025,
+      "name": "insufficientDepositedTokens",
+      "msg": "Insufficient deposited tokens"
+    },
+    {
+      "code": 6026,
+      "name": "invalidTreasury",
+      "msg": "Treasury account does not match oracle configuration"
+    },
+    {
+      "code": 6027,
+      "name": "veLockDisabled",
+      "msg": "Vote-escrow locking is not enabled for this oracle"
+    },
+    {
+      "code": 6028,
+      "name": "invalidLockDuration",
+      "msg": "Lock duration exceeds the maximum allowed"
+    },
+    {
+      "code": 6029,
+      "name": "veLockActive",
+      "msg": "Tokens are locked in vote escrow"
+    },
+    {
+      "code": 6030,
+      "name": "invalidWeightCap",
+      "msg": "Weight cap exceeds the denominator"
+    },
+    {
+      "code": 6031,
+      "name": "selfDelegation",
+      "msg": "Cannot delegate to yourself"
+    },
+    {
+      "code": 6032,
+      "name": "invalidDelegate",
+      "msg": "Delegate account does not match the target"
+    },
+    {
+      "code": 6033,
+      "name": "invalidCommission",
+      "msg": "Commission exceeds the denominator"
+    },
+    {
+      "code": 6034,
+      "name": "undelegationPending",
+      "msg": "Undelegation has already been requested"
+    },
+    {
+      "code": 6035,
+      "name": "delegationActive",
+      "msg": "Delegation is still active"
+    },
+    {
+      "code": 6036,
+      "name": "undelegationLocked",
+      "msg": "Undelegation is still within the locking period"
+    },
+    {
+      "code": 6037,
+      "name": "unauthorizedSubmitter",
+      "msg": "Signer is neither the staker nor its designated submitter"
+    },
+    {
+      "code": 6038,
+      "name": "stakeBelowSubmitMinimum",
+      "msg": "Stake is below the minimum required to submit"
+    },
+    {
+      "code": 6039,
+      "name": "stakeBelowVoteMinimum",
+      "msg": "Stake is below the minimum required to vote"
+    },
+    {
+      "code": 6040,
+      "name": "submissionTooFrequent",
+      "msg": "Submission is too soon after the previous one"
+    },
+    {
+      "code": 6041,
+      "name": "unsupportedMintExtension",
+      "msg": "Weight mint has an unsupported Token-2022 extension"
+    },
+    {
+      "code": 6042,
+      "name": "notNativeOracle",
+      "msg": "Oracle is not weighted by native SOL"
+    },
+    {
+      "code": 6043,
+      "name": "notNftOracle",
+      "msg": "Oracle is not weighted by an NFT collection"
+    },
+    {
+      "code": 6044,
+      "name": "notTokenOracle",
+      "msg": "Oracle is not weighted by a fungible token"
+    },
+    {
+      "code": 6045,
+      "name": "invalidCollection",
+      "msg": "NFT is not a verified member of the oracle's collection"
+    },
+    {
+      "code": 6046,
+      "name": "receiptMintUnavailable",
+      "msg": "Receipt mint already exists or stake was deposited before it"
+    },
+    {
+      "code": 6047,
+      "name": "invalidReceiptMint",
+      "msg": "Account is not the oracle's receipt mint"
+    },
+    {
+      "code": 6048,
+      "name": "receiptAccountsMissing",
+      "msg": "Receipt mint and token accounts are required"
+    },
+    {
+      "code": 6049,
+      "name": "receiptOwnerNotBlacklisted",
+      "msg": "Receipt holder is not blacklisted"
+    },
+    {
+      "code": 6050,
+      "name": "receiptOwnerBlacklisted",
+      "msg": "Receipt holder is blacklisted"
+    },
+    {
+      "code": 6051,
+      "name": "submitterNotPermitted",
+      "msg": "Submitter is not on the oracle's allowlist"
+    },
+    {
+      "code": 6052,
+      "name": "notWhitelisted",
+      "msg": "Account has not been approved by whitelist vote"
+    },
+    {
+      "code": 6053,
+      "name": "unauthorizedRevocation",
+      "msg": "Only the authority may revoke this permit"
+    },
+    {
+      "code": 6054,
+      "name": "governanceDisabled",
+      "msg": "Governance proposals are disabled for this oracle"
+    },
+    {
+      "code": 6055,
+      "name": "invalidProposalId",
+      "msg": "Proposal id must equal the oracle's proposal count"
+    },
+    {
+      "code": 6056,
+      "name": "invalidProposal",
+      "msg": "Proposal belongs to a different oracle"
+    },
+    {
+      "code": 6057,
+      "name": "proposalVotingClosed",
+      "msg": "Voting on this proposal has closed"
+    },
+    {
+      "code": 6058,
+      "name": "proposalNotExecutable",
+      "msg": "Proposal cannot be executed yet"
+    },
+    {
+      "code": 6059,
+      "name": "proposalAlreadyExecuted",
+      "msg": "Proposal has already been executed"
+    },
+    {
+      "code": 6060,
+      "name": "proposalRejected",
+      "msg": "Proposal did not pass"
+    },
+    {
+      "code": 6061,
+      "name": "appealsDisabled",
+      "msg": "Blacklist appeals are disabled for this oracle"
+    },
+    {
+      "code": 6062,
+      "name": "notBlacklisted",
+      "msg": "Account is not blacklisted"
+    },
+    {
+      "code": 6063,
+      "name": "appealTooEarly",
+      "msg": "Minimum blacklist duration has not elapsed"
+    },
+    {
+      "code": 6064,
+      "name": "appealVotingClosed",
+      "msg": "Voting on this appeal has closed"
+    },
+    {
+      "code": 6065,
+      "name": "appealVotingOpen",
+      "msg": "Voting on this appeal is still open"
+    },
+    {
+      "code": 6066,
+      "name": "appellantCannotVote",
+      "msg": "Appellant cannot vote on their own appeal"
+    },
+    {
+      "code": 6067,
+      "name": "invalidQuorum",
+      "msg": "Quorum share must be less than or equal to denominator"
+    },
+    {
+      "code": 6068,
+      "name": "unknownTarget",
+      "msg": "Target has no vote record"
+    },
+    {
+      "code": 6069,
+      "name": "noStakeAtSnapshot",
+      "msg": "No stake was held before the vote's snapshot"
+    },
+    {
+      "code": 6070,
+      "name": "invalidSlashRate",
+      "msg": "Slash rate must be less than or equal to denominator"
+    },
+    {
+      "code": 6071,
+      "name": "disputesDisabled",
+      "msg": "Disputes are disabled for this oracle"
+    },
+    {
+      "code": 6072,
+      "name": "submissionNotFound",
+      "msg": "No recent submission matches the disputed record"
+    },
+    {
+      "code": 6073,
+      "name": "invalidDisputeValue",
+      "msg": "Proposed value must differ from the disputed value"
+    },
+    {
+      "code": 6074,
+      "name": "recordAlreadyDisputed",
+      "msg": "History entry has already been disputed"
+    },
+    {
+      "code": 6075,
+      "name": "invalidDispute",
+      "msg": "Dispute belongs to a different oracle"
+    },
+    {
+      "code": 6076,
+      "name": "disputeVotingClosed",
+      "msg": "Voting on this dispute has closed"
+    },
+    {
+      "code": 6077,
+      "name": "disputeVotingOpen",
+      "msg": "Voting on this dispute is still open"
+    },
+    {
+      "code": 6078,
+      "name": "disputePartyCannotVote",
+      "msg": "Disputer and submitter cannot vote on their dispute"
+    },
+    {
+      "code": 6079,
+      "name": "stakeUnderDispute",
+      "msg": "Stake is locked while a dispute against it is open"
+    },
+    {
+      "code": 6080,
+      "name": "slashAccountsMissing",
+      "msg": "Vault accounts are required to pay slashed stake"
+    },
+    {
+      "code": 6081,
+      "name": "invalidDisputer",
+      "msg": "Token account does not belong to the disputer"
+    },
+    {
+      "code": 6082,
+      "name": "unbondingRequired",
+      "msg": "Withdrawals on this oracle must go through request_withdrawal"
+    },
+    {
+      "code": 6083,
+      "name": "unbondingUnsupported",
+      "msg": "Collection oracles cannot have a withdrawal locking period"
+    },
+    {
+      "code": 6084,
+      "name": "earlyExitDisabled",
+      "msg": "Early withdrawal is disabled for this oracle"
+    },
+    {
+      "code": 6085,
+      "name": "invalidVeBoost",
+      "msg": "Vote-escrow boost exceeds the maximum allowed"
+    },
+    {
+      "code": 6086,
+      "name": "invalidExecutionGrace",
+      "msg": "Governance needs a nonzero proposal execution grace period"
+    },
+    {
+      "code": 6087,
+      "name": "proposalExpired",
+      "msg": "Proposal execution window has passed"
+    },
+    {
+      "code": 6088,
+      "name": "invalidDisputeBond",
+      "msg": "Disputes need a nonzero bond"
+    },
+    {
+      "code": 6089,
+      "name": "receiptsWithDisputes",
+      "msg": "Liquid receipts cannot be combined with disputes"
+    }
+  ],
+  "types": [
+    {
+      "name": "accessMode",
+      "docs": [
+        "Who may call `submit_value`; staking and delegation stay open either way."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "open"
+          },
+          {
+            "name": "permissioned"
+          }
+        ]
+      }
+    },
+    {
+      "name": "appeal",
+      "docs": [
+        "A blacklisted user's bonded request for a fresh vote on their status."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "appellant",
+            "type": "pubkey"
+          },
+          {
+            "name": "bond",
+            "docs": [
+              "Lamports returned if the appeal is granted and forfeited otherwise."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "snapshotSlot",
+            "docs": [
+              "Votes count vote weight held before this slot."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "supportVotes",
+            "type": "u64"
+          },
+          {
+            "name": "opposeVotes",
+            "type": "u64"
+          },
+          {
+            "name": "votingEndsAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "appealOpened",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "appellant",
+            "type": "pubkey"
+          },
+          {
+            "name": "bond",
+            "type": "u64"
+          },
+          {
+            "name": "votingEndsAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "appealResolved",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "appellant",
+            "type": "pubkey"
+          },
+          {
+            "name": "granted",
+            "type": "bool"
+          },
+          {
+            "name": "supportVotes",
+            "type": "u64"
+          },
+          {
+            "name": "opposeVotes",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "appealVote",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "voter",
+            "type": "pubkey"
+          },
+          {
+            "name": "votingEndsAt",
+            "docs": [
+              "`voting_ends_at` of the appeal round this vote was cast in."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "appealVoted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "appellant",
+            "type": "pubkey"
+          },
+          {
+            "name": "voter",
+            "type": "pubkey"
+          },
+          {
+            "name": "support",
+            "type": "bool"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "blacklistStatusChanged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "target",
+            "type": "pubkey"
+          },
+          {
+            "name": "isBlacklisted",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "circuitBreakerReset",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "circuitBreakerTripped",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "submitter",
+            "type": "pubkey"
+          },
+          {
+            "name": "referenceValue",
+            "type": "i128"
+          },
+          {
+            "name": "rejectedValue",
+            "type": "i128"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "decayModel",
+      "docs": [
+        "How a submission's weight fades with age.",
+        "",
+        "`Linear` and `Step` are evaluated per contribution from `PriceHistory`, so",
+        "only the last `MAX_HISTORY_ENTRIES` submissions can count towards the",
+        "aggregate; older ones are dropped even if still inside the window."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "exponential"
+          },
+          {
+            "name": "linear",
+            "fields": [
+              {
+                "name": "windowSeconds",
+                "type": "u64"
+              }
+            ]
+          },
+          {
+            "name": "step",
+            "fields": [
+              {
+                "name": "cutoffSeconds",
+                "type": "u64"
+              }
+            ]
+          },
+          {
+            "name": "none"
+          }
+        ]
+      }
+    },
+    {
+      "name": "delegated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "delegator",
+            "type": "pubkey"
+          },
+          {
+            "name": "delegate",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "delegation",
+      "docs": [
+        "Stake a user has handed to a reporter. Seeded by the delegator, so each",
+        "user delegates to one reporter at a time."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "delegator",
+            "type": "pubkey"
+          },
+          {
+            "name": "delegate",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "rewardIndex",
+            "docs": [
+              "The delegate's `delegation_reward_index` at the last payout."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "availableAt",
+            "docs": [
+              "When undelegated tokens return to the delegator; 0 while active."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "delegationRewardClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "delegator",
+            "type": "pubkey"
+          },
+          {
+            "name": "delegate",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositLot",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "unlockTime",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "dispute",
+      "docs": [
+        "A bonded challenge to one submission in the price history."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "disputer",
+            "type": "pubkey"
+          },
+          {
+            "name": "submitter",
+            "type": "pubkey"
+          },
+          {
+            "name": "recordSequence",
+            "docs": [
+              "Sequence number of the disputed `PriceRecord`."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "recordTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "disputedValue",
+            "type": "i128"
+          },
+          {
+            "name": "proposedValue",
+            "type": "i128"
+          },
+          {
+            "name": "bond",
+            "docs": [
+              "Lamports returned if the dispute is upheld and forfeited otherwise."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "snapshotSlot",
+            "docs": [
+              "Votes count vote weight held before this slot."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "supportVotes",
+            "type": "u64"
+          },
+          {
+            "name": "opposeVotes",
+            "type": "u64"
+          },
+          {
+            "name": "votingEndsAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "disputeOpened",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "disputer",
+            "type": "pubkey"
+          },
+          {
+            "name": "submitter",
+            "type": "pubkey"
+          },
+          {
+            "name": "recordSequence",
+            "type": "u64"
+          },
+          {
+            "name": "recordTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "disputedValue",
+            "type": "i128"
+          },
+          {
+            "name": "proposedValue",
+            "type": "i128"
+          },
+          {
+            "name": "bond",
+            "type": "u64"
+          },
+          {
+            "name": "votingEndsAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "disputeOutcome",
+      "docs": [
+        "Where a history entry stands in the dispute process."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "undisputed"
+          },
+          {
+            "name": "pending"
+          },
+          {
+            "name": "upheld"
+          },
+          {
+            "name": "rejected"
+          }
+        ]
+      }
+    },
+    {
+      "name": "disputeResolved",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "disputer",
+            "type": "pubkey"
+          },
+          {
+            "name": "submitter",
+            "type": "pubkey"
+          },
+          {
+            "name": "recordSequence",
+            "type": "u64"
+          },
+          {
+            "name": "recordTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "upheld",
+            "type": "bool"
+          },
+          {
+            "name": "slashed",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "disputeVote",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "voter",
+            "type": "pubkey"
+          },
+          {
+            "name": "support",
+            "type": "bool"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "disputeVoted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "dispute",
+            "type": "pubkey"
+          },
+          {
+            "name": "voter",
+            "type": "pubkey"
+          },
+          {
+            "name": "support",
+            "type": "bool"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "earlyWithdrawal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "penalty",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "funded",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "from",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "i256",
+      "docs": [
+        "Signed 256-bit integer in sign-magnitude form, see `accumulator`."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "negative",
+            "type": "bool"
+          },
+          {
+            "name": "hi",
+            "type": "u128"
+          },
+          {
+            "name": "lo",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "initializeOracleParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "description",
+            "type": "string"
+          },
+          {
+            "name": "rewardBps",
+            "type": "u64"
+          },
+          {
+            "name": "halfLifeSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "quorum",
+            "docs": [
+              "Vote weight a decision's tally must exceed."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quorumBps",
+            "docs": [
+              "Share of total vote weight, in bps, used instead of `quorum` when",
+              "nonzero."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "depositLockingPeriod",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalLockingPeriod",
+            "type": "u64"
+          },
+          {
+            "name": "alpha",
+            "type": "u64"
+          },
+          {
+            "name": "decayModel",
+            "type": {
+              "defined": {
+                "name": "decayModel"
+              }
+            }
+          },
+          {
+            "name": "earlyExitPenaltyBps",
+            "type": "u64"
+          },
+          {
+            "name": "treasury",
+            "type": "pubkey"
+          },
+          {
+            "name": "veMaxLockSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "veMaxBoostBps",
+            "type": "u64"
+          },
+          {
+            "name": "maxUserWeight",
+            "type": "u64"
+          },
+          {
+            "name": "maxUserWeightBps",
+            "type": "u64"
+          },
+          {
+            "name": "weightingMode",
+            "type": {
+              "defined": {
+                "name": "weightingMode"
+              }
+            }
+          },
+          {
+            "name": "minSubmitStake",
+            "docs": [
+              "Unlocked plus delegated-in tokens needed to call `submit_value`."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minVoteStake",
+            "docs": [
+              "Vote weight needed to vote, read at the decision's snapshot, and to",
+              "open a proposal."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minSubmissionInterval",
+            "docs": [
+              "Seconds a user must wait between their own submissions."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "accessMode",
+            "type": {
+              "defined": {
+                "name": "accessMode"
+              }
+            }
+          },
+          {
+            "name": "proposalVotingPeriod",
+            "docs": [
+              "Seconds a proposal stays open for votes; 0 disables proposals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "proposalExecutionDelay",
+            "docs": [
+              "Seconds between the close of voting and a passed proposal's execution."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "proposalExecutionGrace",
+            "docs": [
+              "Seconds a passed proposal stays executable; required with governance."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "appealBond",
+            "docs": [
+              "Lamports a blacklisted user stakes on an appeal."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "appealVotingPeriod",
+            "docs": [
+              "Seconds an appeal stays open for votes; 0 disables appeals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minBlacklistDuration",
+            "docs": [
+              "Seconds a target must stay blacklisted before it may appeal."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "disputeBond",
+            "docs": [
+              "Lamports a disputer stakes on a challenge to a reported value;",
+              "required once disputes are enabled."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "disputeVotingPeriod",
+            "docs": [
+              "Seconds a dispute stays open for votes; 0 disables disputes. Not",
+              "available on oracles with a receipt mint."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "disputeSlashBps",
+            "docs": [
+              "Share of the submitter's stake paid to the disputer when a dispute is",
+              "upheld."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "heartbeatSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "maxUpdateDeviationBps",
+            "type": "u64"
+          },
+          {
+            "name": "maxWindowDeviationBps",
+            "type": "u64"
+          },
+          {
+            "name": "deviationWindowSeconds",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "nftDeposited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "nftLock",
+      "docs": [
+        "When one escrowed NFT may be withdrawn, set from the deposit locking",
+        "period at deposit."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "unlockTime",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "nftWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "oracleStale",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "lastSubmissionTime",
+            "type": "i64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "oracleState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "weightMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "rewardBps",
+            "type": "u64"
+          },
+          {
+            "name": "halfLifeSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "decayModel",
+            "type": {
+              "defined": {
+                "name": "decayModel"
+              }
+            }
+          },
+          {
+            "name": "quorum",
+            "docs": [
+              "Vote weight a decision's tally must exceed."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quorumBps",
+            "docs": [
+              "Quorum as a share of `total_vote_weight`, replacing `quorum` when",
+              "nonzero."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "depositLockingPeriod",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalLockingPeriod",
+            "docs": [
+              "Unbonding time for `request_withdrawal`; 0 allows direct withdrawals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "earlyExitPenaltyBps",
+            "docs": [
+              "Share of an early withdrawal kept as a penalty; 0 disables early exit."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "treasury",
+            "docs": [
+              "Token account that receives early-exit penalties; a wallet on native",
+              "oracles."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "veMaxLockSeconds",
+            "docs": [
+              "Longest vote-escrow lock a staker can choose; 0 disables boosting."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "veMaxBoostBps",
+            "docs": [
+              "Extra weight, in `DENOMINATOR` units, for a lock of the full length.",
+              "At most `MAX_VE_BOOST_BPS`."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxUserWeight",
+            "docs": [
+              "Absolute ceiling on a single user's effective weight; 0 disables."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxUserWeightBps",
+            "docs": [
+              "Ceiling as a share of `total_deposited_tokens`; 0 disables."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "weightingMode",
+            "type": {
+              "defined": {
+                "name": "weightingMode"
+              }
+            }
+          },
+          {
+            "name": "minSubmitStake",
+            "docs": [
+              "Unlocked plus delegated-in tokens needed to call `submit_value`."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minVoteStake",
+            "docs": [
+              "Vote weight needed to vote, read at the decision's snapshot, and to",
+              "open a proposal."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minSubmissionInterval",
+            "docs": [
+              "Seconds a user must wait between their own submissions."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "accessMode",
+            "type": {
+              "defined": {
+                "name": "accessMode"
+              }
+            }
+          },
+          {
+            "name": "proposalVotingPeriod",
+            "docs": [
+              "Seconds a proposal stays open for votes; 0 disables proposals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "proposalExecutionDelay",
+            "docs": [
+              "Seconds between the close of voting and a passed proposal's execution."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "proposalExecutionGrace",
+            "docs": [
+              "Seconds a passed proposal stays executable; required with governance."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "appealBond",
+            "docs": [
+              "Lamports a blacklisted user stakes on an appeal."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "appealVotingPeriod",
+            "docs": [
+              "Seconds an appeal stays open for votes; 0 disables appeals."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minBlacklistDuration",
+            "docs": [
+              "Seconds a target must stay blacklisted before it may appeal."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "disputeBond",
+            "docs": [
+              "Lamports a disputer stakes on a challenge to a reported value;",
+              "required once disputes are enabled."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "disputeVotingPeriod",
+            "docs": [
+              "Seconds a dispute stays open for votes; 0 disables disputes. Not",
+              "available on oracles with a receipt mint."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "disputeSlashBps",
+            "docs": [
+              "Share of the submitter's stake paid to the disputer when a dispute is",
+              "upheld."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "alpha",
+            "type": "u64"
+          },
+          {
+            "name": "heartbeatSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "status",
+            "type": {
+              "defined": {
+                "name": "oracleStatus"
+              }
+            }
+          },
+          {
+            "name": "maxUpdateDeviationBps",
+            "type": "u64"
+          },
+          {
+            "name": "maxWindowDeviationBps",
+            "type": "u64"
+          },
+          {
+            "name": "deviationWindowSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "breakerResetTime",
+            "type": "i64"
+          },
+          {
+            "name": "aggregatedValue",
+            "type": "i128"
+          },
+          {
+            "name": "latestValue",
+            "type": "i128"
+          },
+          {
+            "name": "aggregatedWeight",
+            "type": "u128"
+          },
+          {
+            "name": "weightedSum",
+            "type": {
+              "defined": {
+                "name": "i256"
+              }
+            }
+          },
+          {
+            "name": "lastSubmissionTime",
+            "type": "i64"
+          },
+          {
+            "name": "lastTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "totalDepositedTokens",
+            "type": "u64"
+          },
+          {
+            "name": "totalVoteWeight",
+            "docs": [
+              "Sum of every staker's `vote_weight`, in the same units as the vote",
+              "tallies; `quorum_bps` is a share of it."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "priorVoteWeight",
+            "docs": [
+              "`total_vote_weight` before the changes made in",
+              "`vote_weight_updated_slot`."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "voteWeightUpdatedSlot",
+            "type": "u64"
+          },
+          {
+            "name": "stakeKind",
+            "type": {
+              "defined": {
+                "name": "stakeKind"
+              }
+            }
+          },
+          {
+            "name": "nativeVaultBump",
+            "docs": [
+              "Bump of the lamport vault; only set on native oracles."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "delegatorRewardsReserved",
+            "docs": [
+              "Lamports earned by delegators but not yet claimed; excluded from the",
+              "reward pool."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "receiptMint",
+            "docs": [
+              "Liquid receipt mint tracking staked tokens; default when disabled."
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "proposalCount",
+            "docs": [
+              "Id the next proposal must use."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "name",
+            "type": "string"
+          },
+          {
+            "name": "description",
+            "type": "string"
+          },
+          {
+            "name": "targets",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "targetVotes"
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "oracleStatus",
+      "docs": [
+        "Whether consumers should trust `aggregated_value`."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "active"
+          },
+          {
+            "name": "stale"
+          },
+          {
+            "name": "halted"
+          }
+        ]
+      }
+    },
+    {
+      "name": "parameterUpdate",
+      "docs": [
+        "Parameters a proposal may change; `None` leaves a field as it is. Mint,",
+        "authority and vote-escrow settings are fixed for the oracle's lifetime."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rewardBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "halfLifeSeconds",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "decayModel",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "decayModel"
+                }
+              }
+            }
+          },
+          {
+            "name": "quorum",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "quorumBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "depositLockingPeriod",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "withdrawalLockingPeriod",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "earlyExitPenaltyBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "treasury",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "alpha",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "heartbeatSeconds",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxUpdateDeviationBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxWindowDeviationBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "deviationWindowSeconds",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxUserWeight",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "maxUserWeightBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "weightingMode",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "weightingMode"
+                }
+              }
+            }
+          },
+          {
+            "name": "minSubmitStake",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "minVoteStake",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "minSubmissionInterval",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "accessMode",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "accessMode"
+                }
+              }
+            }
+          },
+          {
+            "name": "proposalVotingPeriod",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "proposalExecutionDelay",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "proposalExecutionGrace",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "appealBond",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "appealVotingPeriod",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "minBlacklistDuration",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "disputeBond",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "disputeVotingPeriod",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "disputeSlashBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "resetCircuitBreaker",
+            "docs": [
+              "Resumes a halted oracle, replacing the authority's breaker reset."
+            ],
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6012,
-      "name": "alreadyVoted",
-      "msg": "User has already voted on this target"
+      "name": "pendingWithdrawal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "availableAt",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6013,
-      "name": "tooManyVotes",
-      "msg": "Exceeded vote capacity for user"
+      "name": "priceHistory",
+      "docs": [
+        "Rolling log of accepted submissions, oldest first. Kept in its own PDA so",
+        "`OracleState` stays well under the account size limit."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
+          },
+          {
+            "name": "records",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "priceRecord"
+                }
+              }
+            }
+          },
+          {
+            "name": "nextSequence",
+            "docs": [
+              "Sequence number the next record will be given."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6014,
-      "name": "tooManyTargets",
-      "msg": "Exceeded governance target capacity"
+      "name": "priceRecord",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sequence",
+            "docs": [
+              "Position of this record among all records ever pushed, so a dispute",
+              "can name it even when other fields repeat."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          },
+          {
+            "name": "aggregatedValue",
+            "type": "i128"
+          },
+          {
+            "name": "latestValue",
+            "type": "i128"
+          },
+          {
+            "name": "dispute",
+            "type": {
+              "defined": {
+                "name": "disputeOutcome"
+              }
+            }
+          },
+          {
+            "name": "weight",
+            "docs": [
+              "Effective weight the submitter contributed."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "superseded",
+            "docs": [
+              "Set once the same staker submits again and this value stops counting."
+            ],
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6015,
-      "name": "invalidAuthority",
-      "msg": "Account authority does not match expected value"
-    }
-  ],
-  "types": [
-    {
-      "name": "blacklistStatusChanged",
+      "name": "proposal",
+      "docs": [
+        "A stake-voted change to oracle parameters, executable by anyone once",
+        "voting has closed, the delay has passed and it cleared quorum."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "target",
+            "name": "oracle",
             "type": "pubkey"
           },
           {
-            "name": "isBlacklisted",
+            "name": "id",
+            "type": "u64"
+          },
+          {
+            "name": "proposer",
+            "type": "pubkey"
+          },
+          {
+            "name": "update",
+            "type": {
+              "defined": {
+                "name": "parameterUpdate"
+              }
+            }
+          },
+          {
+            "name": "snapshotSlot",
+            "docs": [
+              "Votes count vote weight held before this slot."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "yesVotes",
+            "type": "u64"
+          },
+          {
+            "name": "noVotes",
+            "type": "u64"
+          },
+          {
+            "name": "votingEndsAt",
+            "type": "i64"
+          },
+          {
+            "name": "executableAt",
+            "type": "i64"
+          },
+          {
+            "name": "expiresAt",
+            "docs": [
+              "A passed proposal left unexecuted until this time lapses, so stale",
+              "parameters cannot be applied long after the vote."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "executed",
             "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "funded",
+      "name": "proposalCreated",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "from",
+            "name": "oracle",
             "type": "pubkey"
           },
           {
-            "name": "amount",
+            "name": "proposal",
+            "type": "pubkey"
+          },
+          {
+            "name": "id",
             "type": "u64"
+          },
+          {
+            "name": "proposer",
+            "type": "pubkey"
+          },
+          {
+            "name": "votingEndsAt",
+            "type": "i64"
           }
         ]
       }
     },
     {
-      "name": "initializeOracleParams",
+      "name": "proposalExecuted",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "name",
-            "type": "string"
+            "name": "oracle",
+            "type": "pubkey"
           },
           {
-            "name": "description",
-            "type": "string"
+            "name": "proposal",
+            "type": "pubkey"
           },
           {
-            "name": "rewardBps",
+            "name": "id",
             "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "proposalVote",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "proposal",
+            "type": "pubkey"
           },
           {
-            "name": "halfLifeSeconds",
-            "type": "u64"
+            "name": "voter",
+            "type": "pubkey"
           },
           {
-            "name": "quorum",
-            "type": "u64"
+            "name": "support",
+            "type": "bool"
           },
           {
-            "name": "depositLockingPeriod",
+            "name": "weight",
             "type": "u64"
           },
           {
-            "name": "withdrawalLockingPeriod",
-            "type": "u64"
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "proposalVoted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "proposal",
+            "type": "pubkey"
           },
           {
-            "name": "alpha",
+            "name": "voter",
+            "type": "pubkey"
+          },
+          {
+            "name": "support",
+            "type": "bool"
+          },
+          {
+            "name": "weight",
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "oracleState",
+      "name": "receiptMintCreated",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "authority",
+            "name": "oracle",
             "type": "pubkey"
           },
           {
-            "name": "weightMint",
+            "name": "receiptMint",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "receiptsFrozenChanged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
             "type": "pubkey"
           },
           {
-            "name": "rewardBps",
-            "type": "u64"
+            "name": "account",
+            "type": "pubkey"
           },
           {
-            "name": "halfLifeSeconds",
+            "name": "frozen",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakeCheckpoint",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "slot",
             "type": "u64"
           },
           {
-            "name": "quorum",
+            "name": "weight",
             "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakeKind",
+      "docs": [
+        "What stakers lock up for weight. Amounts in `UserState` count lamports on",
+        "native oracles and NFTs on collection oracles."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "token"
           },
           {
-            "name": "depositLockingPeriod",
-            "type": "u64"
+            "name": "native"
           },
           {
-            "name": "withdrawalLockingPeriod",
+            "name": "nftCollection"
+          }
+        ]
+      }
+    },
+    {
+      "name": "submissionRecord",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sequence",
+            "docs": [
+              "Sequence number of the matching `PriceRecord`."
+            ],
             "type": "u64"
           },
           {
-            "name": "alpha",
-            "type": "u64"
+            "name": "timestamp",
+            "type": "i64"
           },
           {
-            "name": "aggregatedValue",
+            "name": "value",
             "type": "i128"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "submitterChanged",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "latestValue",
-            "type": "i128"
+            "name": "owner",
+            "type": "pubkey"
           },
           {
-            "name": "aggregatedWeight",
-            "type": "u128"
+            "name": "submitter",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "submitterPermit",
+      "docs": [
+        "Lets `submitter` report to a permissioned oracle. Granted by the authority",
+        "or claimed by a user the whitelist vote has approved."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oracle",
+            "type": "pubkey"
           },
           {
-            "name": "lastSubmissionTime",
-            "type": "i64"
+            "name": "submitter",
+            "type": "pubkey"
           },
           {
-            "name": "lastTimestamp",
-            "type": "i64"
+            "name": "payer",
+            "docs": [
+              "Paid the rent and receives it back on revocation."
+            ],
+            "type": "pubkey"
           },
           {
-            "name": "totalDepositedTokens",
-            "type": "u64"
+            "name": "viaVote",
+            "type": "bool"
           },
           {
             "name": "bump",
             "type": "u8"
-          },
-          {
-            "name": "name",
-            "type": "string"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "submitterPermitGranted",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "description",
-            "type": "string"
+            "name": "oracle",
+            "type": "pubkey"
           },
           {
-            "name": "priceHistory",
-            "type": {
-              "vec": {
-                "defined": {
-                  "name": "priceRecord"
-                }
-              }
-            }
+            "name": "submitter",
+            "type": "pubkey"
           },
           {
-            "name": "targets",
-            "type": {
-              "vec": {
-                "defined": {
-                  "name": "targetVotes"
-                }
-              }
-            }
+            "name": "viaVote",
+            "type": "bool"
           }
         ]
       }
     },
     {
-      "name": "priceRecord",
+      "name": "submitterPermitRevoked",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "timestamp",
-            "type": "i64"
-          },
-          {
-            "name": "aggregatedValue",
-            "type": "i128"
+            "name": "oracle",
+            "type": "pubkey"
           },
           {
-            "name": "latestValue",
-            "type": "i128"
+            "name": "submitter",
+            "type": "pubkey"
           }
         ]
       }
@@ -1503,6 +7369,29 @@ export type Oracle = {
           {
             "name": "isBlacklisted",
             "type": "bool"
+          },
+          {
+            "name": "blacklistedAt",
+            "docs": [
+              "When the target was last blacklisted."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "pardonRound",
+            "docs": [
+              "Bumped by each granted appeal. Blacklist votes cast in an earlier",
+              "round were cleared from the tally and no longer count."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "snapshotSlot",
+            "docs": [
+              "Votes count vote weight held before this slot: the first vote on the",
+              "target, or the last granted appeal."
+            ],
+            "type": "u64"
           }
         ]
       }
@@ -1539,6 +7428,30 @@ export type Oracle = {
         ]
       }
     },
+    {
+      "name": "undelegated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "delegator",
+            "type": "pubkey"
+          },
+          {
+            "name": "delegate",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "availableAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
     {
       "name": "userState",
       "type": {
@@ -1560,10 +7473,6 @@ export type Oracle = {
             "name": "unlockedTokens",
             "type": "u64"
           },
-          {
-            "name": "depositTimestamp",
-            "type": "i64"
-          },
           {
             "name": "lastOperationTimestamp",
             "type": "i64"
@@ -1580,6 +7489,57 @@ export type Oracle = {
             "name": "weight",
             "type": "u64"
           },
+          {
+            "name": "veLockEnd",
+            "docs": [
+              "End of the vote-escrow lock chosen at deposit; no withdrawals before it."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "delegatedIn",
+            "docs": [
+              "Stake other users have delegated to this reporter."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "delegatedOut",
+            "docs": [
+              "Own stake held in this user's `Delegation`, until the undelegation",
+              "completes."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "commissionBps",
+            "docs": [
+              "Share of delegators' rewards this reporter keeps, in `DENOMINATOR` units."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "delegationRewardIndex",
+            "docs": [
+              "Cumulative delegator reward per delegated token, scaled by `WAD`."
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "nftCount",
+            "docs": [
+              "NFTs held in escrow for this user on collection-weighted oracles,",
+              "including any still locked."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "submitter",
+            "docs": [
+              "Hot key allowed to submit and vote on the owner's behalf."
+            ],
+            "type": "pubkey"
+          },
           {
             "name": "initialized",
             "type": "bool"
@@ -1607,6 +7567,71 @@ export type Oracle = {
                 }
               }
             }
+          },
+          {
+            "name": "depositLots",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "depositLot"
+                }
+              }
+            }
+          },
+          {
+            "name": "pendingWithdrawals",
+            "docs": [
+              "Tokens waiting out the unbonding period. They no longer carry weight",
+              "and are already excluded from `total_deposited_tokens`."
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "pendingWithdrawal"
+                }
+              }
+            }
+          },
+          {
+            "name": "stakeCheckpoints",
+            "docs": [
+              "`vote_weight` after each change, oldest first; see `checkpoints`."
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "stakeCheckpoint"
+                }
+              }
+            }
+          },
+          {
+            "name": "recentSubmissions",
+            "docs": [
+              "Newest submissions, oldest first; lets a dispute name its submitter."
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "submissionRecord"
+                }
+              }
+            }
+          },
+          {
+            "name": "openDisputes",
+            "docs": [
+              "Open disputes against this user's submissions. Stake cannot leave",
+              "while any are pending."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "voteWeight",
+            "docs": [
+              "Weight last counted into the oracle's `total_vote_weight`."
+            ],
+            "type": "u64"
           }
         ]
       }
@@ -1623,6 +7648,13 @@ export type Oracle = {
           {
             "name": "weight",
             "type": "u64"
+          },
+          {
+            "name": "round",
+            "docs": [
+              "The target's `pardon_round` when the vote was cast."
+            ],
+            "type": "u32"
           }
         ]
       }
@@ -1652,6 +7684,13 @@ export type Oracle = {
             "name": "weight",
             "type": "u64"
           },
+          {
+            "name": "cappedWeight",
+            "docs": [
+              "Tokens above the per-user cap that were not counted."
+            ],
+            "type": "u64"
+          },
           {
             "name": "rewardLamports",
             "type": "u64"
@@ -1659,6 +7698,26 @@ export type Oracle = {
         ]
       }
     },
+    {
+      "name": "voteWeightUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "weight",
+            "type": "u64"
+          },
+          {
+            "name": "cappedWeight",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "voted",
       "type": {
@@ -1682,6 +7741,43 @@ export type Oracle = {
           }
         ]
       }
+    },
+    {
+      "name": "weightingMode",
+      "docs": [
+        "How a staker's capped token weight maps to submission and voting power."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "linear"
+          },
+          {
+            "name": "squareRoot"
+          }
+        ]
+      }
+    },
+    {
+      "name": "withdrawalRequested",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "availableAt",
+            "type": "i64"
+          }
+        ]
+      }
     }
   ]
 };
@@ -3,14 +3,25 @@ import { Program } from '@coral-xyz/anchor'
 import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js'
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  AccountState,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
+  createInitializeDefaultAccountStateInstruction,
+  createInitializeMintInstruction,
+  createInitializeNonTransferableMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializeTransferHookInstruction,
   createMint,
   getAccount,
   getAssociatedTokenAddress,
+  getMintLen,
   getOrCreateAssociatedTokenAccount,
   mintTo,
 } from '@solana/spl-token'
 import { Oracle } from '../target/types/oracle'
+import nftHolderSecret from './fixtures/nft-holder.json'
 
 describe('oracle program integration', () => {
   const provider = anchor.AnchorProvider.env()
@@ -19,11 +30,194 @@ describe('oracle program integration', () => {
   const program = anchor.workspace.Oracle as Program<Oracle>
   const wallet = provider.wallet as anchor.Wallet & { payer: anchor.web3.Keypair }
 
+  type InitializeParams = Parameters<typeof program.methods.initialize>[0]
+
+  // Every oracle parameter at its neutral value; tests override what they exercise.
+  const defaultParams = (overrides: Partial<InitializeParams>): InitializeParams => ({
+    name: 'Test Oracle',
+    description: 'Oracle initialized in automated test suite',
+    rewardBps: new anchor.BN(0),
+    halfLifeSeconds: new anchor.BN(120),
+    quorum: new anchor.BN(0),
+    quorumBps: new anchor.BN(0),
+    depositLockingPeriod: new anchor.BN(0),
+    withdrawalLockingPeriod: new anchor.BN(0),
+    alpha: new anchor.BN(1),
+    decayModel: { exponential: {} },
+    earlyExitPenaltyBps: new anchor.BN(0),
+    treasury: PublicKey.default,
+    heartbeatSeconds: new anchor.BN(0),
+    maxUpdateDeviationBps: new anchor.BN(0),
+    maxWindowDeviationBps: new anchor.BN(0),
+    deviationWindowSeconds: new anchor.BN(0),
+    veMaxLockSeconds: new anchor.BN(0),
+    veMaxBoostBps: new anchor.BN(0),
+    maxUserWeight: new anchor.BN(0),
+    maxUserWeightBps: new anchor.BN(0),
+    weightingMode: { linear: {} },
+    minSubmitStake: new anchor.BN(0),
+    minVoteStake: new anchor.BN(0),
+    minSubmissionInterval: new anchor.BN(0),
+    accessMode: { open: {} },
+    proposalVotingPeriod: new anchor.BN(0),
+    proposalExecutionDelay: new anchor.BN(0),
+    proposalExecutionGrace: new anchor.BN(0),
+    appealBond: new anchor.BN(0),
+    appealVotingPeriod: new anchor.BN(0),
+    minBlacklistDuration: new anchor.BN(0),
+    disputeBond: new anchor.BN(0),
+    disputeVotingPeriod: new anchor.BN(0),
+    disputeSlashBps: new anchor.BN(0),
+    ...overrides,
+  })
+
+  const historyOf = (oracle: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from('history'), oracle.toBuffer()], program.programId)[0]
+
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms))
+
+  // Waits for a slot after every transaction sent so far, so weight synced
+  // by them counts at any snapshot taken from here on.
+  const nextSlot = async () => {
+    const current = await provider.connection.getSlot()
+    while ((await provider.connection.getSlot()) <= current) {
+      await sleep(100)
+    }
+  }
+
+  const fundedKeypair = async () => {
+    const keypair = anchor.web3.Keypair.generate()
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(keypair.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+    )
+    return keypair
+  }
+
+  // A Token-2022 mint carrying `extensions`, initialized by `extensionInstructions`.
+  const createToken2022Mint = async (
+    extensions: ExtensionType[],
+    extensionInstructions: (mint: PublicKey) => anchor.web3.TransactionInstruction[],
+  ) => {
+    const mint = anchor.web3.Keypair.generate()
+    const space = getMintLen(extensions)
+    const transaction = new anchor.web3.Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: wallet.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      ...extensionInstructions(mint.publicKey),
+      createInitializeMintInstruction(mint.publicKey, 6, wallet.publicKey, wallet.publicKey, TOKEN_2022_PROGRAM_ID),
+    )
+    await provider.sendAndConfirm(transaction, [mint])
+    return mint.publicKey
+  }
+
+  // A fresh token oracle owned by the test wallet, with helpers for staking into it.
+  const setupTokenOracle = async (
+    overrides: Partial<InitializeParams>,
+    existingMint?: PublicKey,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  ) => {
+    const mint = existingMint ?? (await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 6))
+    const [oracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle'), wallet.publicKey.toBuffer(), mint.toBuffer()],
+      program.programId,
+    )
+    const vault = await getAssociatedTokenAddress(mint, oracle, true, tokenProgram)
+    await program.methods
+      .initialize(defaultParams(overrides))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        weightMint: mint,
+        oracleState: oracle,
+        oracleVault: vault,
+        systemProgram: SystemProgram.programId,
+        tokenProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc()
+
+    const userStateOf = (owner: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from('user'), oracle.toBuffer(), owner.toBuffer()],
+        program.programId,
+      )[0]
+    const tokenAccountOf = async (owner: PublicKey) =>
+      (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          wallet.payer,
+          mint,
+          owner,
+          false,
+          undefined,
+          undefined,
+          tokenProgram,
+        )
+      ).address
+    const stakeAccounts = async (staker: anchor.web3.Keypair) => ({
+      user: staker.publicKey,
+      weightMint: mint,
+      oracleState: oracle,
+      userTokenAccount: await tokenAccountOf(staker.publicKey),
+      oracleVault: vault,
+      userState: userStateOf(staker.publicKey),
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    const deposit = async (staker: anchor.web3.Keypair, amount: number) => {
+      await mintTo(
+        provider.connection,
+        wallet.payer,
+        mint,
+        await tokenAccountOf(staker.publicKey),
+        wallet.publicKey,
+        amount,
+        [],
+        undefined,
+        tokenProgram,
+      )
+      await program.methods
+        .depositTokens(new anchor.BN(amount))
+        .accounts((await stakeAccounts(staker)) as never)
+        .signers([staker])
+        .rpc()
+    }
+    const withdraw = async (staker: anchor.web3.Keypair, amount: number) =>
+      program.methods
+        .withdrawTokens(new anchor.BN(amount))
+        .accounts((await stakeAccounts(staker)) as never)
+        .signers([staker])
+        .rpc()
+    const submit = (staker: anchor.web3.Keypair, value: number) =>
+      program.methods
+        .submitValue(new anchor.BN(value))
+        .accounts({
+          user: staker.publicKey,
+          oracleState: oracle,
+          userState: userStateOf(staker.publicKey),
+          owner: staker.publicKey,
+        } as never)
+        .signers([staker])
+        .rpc()
+    const fetchState = () => program.account.oracleState.fetch(oracle)
+
+    return { mint, oracle, vault, userStateOf, tokenAccountOf, stakeAccounts, deposit, withdraw, submit, fetchState }
+  }
+
   let weightMint: PublicKey
   let oracleState: PublicKey
   let oracleVault: PublicKey
   let userTokenAccount: PublicKey
   let userState: PublicKey
+  let treasuryTokenAccount: PublicKey
 
   const depositAmount = 1_000_000
   const submissionValue = new anchor.BN(123456)
@@ -39,6 +233,14 @@ describe('oracle program integration', () => {
     )
     userTokenAccount = userToken.address
 
+    const treasuryToken = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      weightMint,
+      anchor.web3.Keypair.generate().publicKey,
+    )
+    treasuryTokenAccount = treasuryToken.address
+
     await mintTo(
       provider.connection,
       wallet.payer,
@@ -63,16 +265,17 @@ describe('oracle program integration', () => {
   })
 
   it('initializes oracle state', async () => {
-    const params = {
+    const params = defaultParams({
       name: 'Integration Test Oracle',
       description: 'Oracle initialized in automated test suite',
       rewardBps: new anchor.BN(500),
-      halfLifeSeconds: new anchor.BN(120),
       quorum: new anchor.BN(100),
-      depositLockingPeriod: new anchor.BN(0),
-      withdrawalLockingPeriod: new anchor.BN(0),
-      alpha: new anchor.BN(1),
-    }
+      earlyExitPenaltyBps: new anchor.BN(10_000),
+      treasury: treasuryTokenAccount,
+      heartbeatSeconds: new anchor.BN(1),
+      veMaxLockSeconds: new anchor.BN(3_600),
+      veMaxBoostBps: new anchor.BN(100_000),
+    })
 
     const signature = await program.methods
       .initialize(params)
@@ -102,7 +305,10 @@ describe('oracle program integration', () => {
     expect(state.depositLockingPeriod.eq(params.depositLockingPeriod)).toBe(true)
     expect(state.withdrawalLockingPeriod.eq(params.withdrawalLockingPeriod)).toBe(true)
     expect(state.alpha.eq(params.alpha)).toBe(true)
-    expect(state.priceHistory.length).toBe(0)
+    expect(state.decayModel).toEqual({ exponential: {} })
+    expect(state.heartbeatSeconds.eq(params.heartbeatSeconds)).toBe(true)
+    expect(state.status).toEqual({ active: {} })
+    expect((await program.account.priceHistory.fetch(historyOf(oracleState))).records.length).toBe(0)
     expect(state.targets.length).toBe(0)
 
     const vaultAccount = await getAccount(provider.connection, oracleVault)
@@ -135,6 +341,8 @@ describe('oracle program integration', () => {
     const userAccount = await program.account.userState.fetch(userState)
     expect(userAccount.lockedTokens.toNumber()).toBe(depositAmount)
     expect(userAccount.unlockedTokens.toNumber()).toBe(0)
+    expect(userAccount.depositLots.length).toBe(1)
+    expect(userAccount.depositLots[0].amount.toNumber()).toBe(depositAmount)
 
     const vaultAccount = await getAccount(provider.connection, oracleVault)
     expect(Number(vaultAccount.amount)).toBe(depositAmount)
@@ -161,6 +369,7 @@ describe('oracle program integration', () => {
         user: wallet.publicKey,
         oracleState,
         userState,
+        owner: wallet.publicKey,
       })
       .rpc()
 
@@ -168,13 +377,16 @@ describe('oracle program integration', () => {
 
     const state = await program.account.oracleState.fetch(oracleState)
     expect(state.latestValue.eq(submissionValue)).toBe(true)
-    expect(state.priceHistory.length).toBeGreaterThan(0)
-    const lastRecord = state.priceHistory[state.priceHistory.length - 1]
+    const history = await program.account.priceHistory.fetch(historyOf(oracleState))
+    expect(history.records.length).toBeGreaterThan(0)
+    const lastRecord = history.records[history.records.length - 1]
     expect(lastRecord.latestValue.eq(submissionValue)).toBe(true)
 
     const userAccount = await program.account.userState.fetch(userState)
     expect(userAccount.lastSubmittedPrice.eq(submissionValue)).toBe(true)
     expect(userAccount.unlockedTokens.toNumber()).toBe(depositAmount)
+    expect(userAccount.lockedTokens.toNumber()).toBe(0)
+    expect(userAccount.depositLots.length).toBe(0)
   }, 60000)
 
   it('allows submitting multiple oracle values sequentially', async () => {
@@ -190,6 +402,7 @@ describe('oracle program integration', () => {
           user: wallet.publicKey,
           oracleState,
           userState,
+          owner: wallet.publicKey,
         })
         .rpc()
     } catch (error: any) {
@@ -201,8 +414,9 @@ describe('oracle program integration', () => {
 
     const state = await program.account.oracleState.fetch(oracleState)
     expect(state.latestValue.eq(nextValue)).toBe(true)
-    expect(state.priceHistory.length).toBeGreaterThan(1)
-    const lastRecord = state.priceHistory[state.priceHistory.length - 1]
+    const history = await program.account.priceHistory.fetch(historyOf(oracleState))
+    expect(history.records.length).toBeGreaterThan(1)
+    const lastRecord = history.records[history.records.length - 1]
     expect(lastRecord.latestValue.eq(nextValue)).toBe(true)
 
     const userAccount = await program.account.userState.fetch(userState)
@@ -211,6 +425,7 @@ describe('oracle program integration', () => {
   }, 60000)
 
   it('casts whitelist and blacklist votes', async () => {
+    await nextSlot()
     const whitelistTarget = anchor.web3.Keypair.generate().publicKey
     const whitelistSignature = await program.methods
       .voteWhitelist(whitelistTarget)
@@ -250,4 +465,1853 @@ describe('oracle program integration', () => {
     expect(blacklistRecord).toBeDefined()
     expect(blacklistRecord?.blacklistVotes.toNumber()).toBe(depositAmount)
   }, 60000)
+
+  it('marks the oracle stale after a missed heartbeat and clears it on submit', async () => {
+    await new Promise((resolve) => setTimeout(resolve, 3000))
+
+    await program.methods.poke().accounts({ oracleState }).rpc()
+
+    const staleState = await program.account.oracleState.fetch(oracleState)
+    expect(staleState.status).toEqual({ stale: {} })
+
+    await program.methods
+      .submitValue(submissionValue)
+      .accounts({
+        user: wallet.publicKey,
+        oracleState,
+        userState,
+        owner: wallet.publicKey,
+      })
+      .rpc()
+
+    const freshState = await program.account.oracleState.fetch(oracleState)
+    expect(freshState.status).toEqual({ active: {} })
+  }, 60000)
+
+  it('queues a withdrawal and completes it after unbonding', async () => {
+    const amount = new anchor.BN(1_000)
+    const before = await program.account.userState.fetch(userState)
+
+    await program.methods
+      .requestWithdrawal(amount)
+      .accounts({
+        user: wallet.publicKey,
+        oracleState,
+        userState,
+      })
+      .rpc()
+
+    const queued = await program.account.userState.fetch(userState)
+    expect(queued.unlockedTokens.toNumber()).toBe(before.unlockedTokens.toNumber() - amount.toNumber())
+    expect(queued.pendingWithdrawals.length).toBe(1)
+    expect(queued.pendingWithdrawals[0].amount.eq(amount)).toBe(true)
+
+    const vaultBefore = await getAccount(provider.connection, oracleVault)
+    await program.methods
+      .completeWithdrawal()
+      .accounts({
+        user: wallet.publicKey,
+        weightMint,
+        oracleState,
+        userTokenAccount,
+        oracleVault,
+        userState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc()
+
+    const completed = await program.account.userState.fetch(userState)
+    expect(completed.pendingWithdrawals.length).toBe(0)
+    const vaultAfter = await getAccount(provider.connection, oracleVault)
+    expect(Number(vaultBefore.amount) - Number(vaultAfter.amount)).toBe(amount.toNumber())
+  }, 60000)
+
+  it('withdraws early and pays the penalty to the treasury', async () => {
+    const amount = new anchor.BN(10_000)
+    const vaultBefore = await getAccount(provider.connection, oracleVault)
+
+    await program.methods
+      .withdrawEarly(amount)
+      .accounts({
+        user: wallet.publicKey,
+        weightMint,
+        oracleState,
+        userTokenAccount,
+        oracleVault,
+        treasuryTokenAccount,
+        userState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc()
+
+    const vaultAfter = await getAccount(provider.connection, oracleVault)
+    expect(Number(vaultBefore.amount) - Number(vaultAfter.amount)).toBe(amount.toNumber())
+    const treasury = await getAccount(provider.connection, treasuryTokenAccount)
+    expect(Number(treasury.amount)).toBe(1_000)
+  }, 60000)
+
+  it('locks a deposit in vote escrow and blocks withdrawals until expiry', async () => {
+    await program.methods
+      .depositTokensLocked(new anchor.BN(1_000), new anchor.BN(3_600))
+      .accounts({
+        user: wallet.publicKey,
+        weightMint,
+        oracleState,
+        userTokenAccount,
+        oracleVault,
+        userState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc()
+
+    const locked = await program.account.userState.fetch(userState)
+    expect(locked.veLockEnd.toNumber()).toBeGreaterThan(Math.floor(Date.now() / 1000))
+
+    await expect(
+      program.methods
+        .withdrawTokens(new anchor.BN(1))
+        .accounts({
+          user: wallet.publicKey,
+          weightMint,
+          oracleState,
+          userTokenAccount,
+          oracleVault,
+          userState,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc(),
+    ).rejects.toThrow()
+  }, 60000)
+
+  it('delegates stake to a reporter and undelegates it', async () => {
+    const reporter = anchor.web3.Keypair.generate()
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(reporter.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+    )
+    const reporterToken = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      weightMint,
+      reporter.publicKey,
+    )
+    await mintTo(provider.connection, wallet.payer, weightMint, reporterToken.address, wallet.publicKey, 1_000)
+
+    const [reporterState] = PublicKey.findProgramAddressSync(
+      [Buffer.from('user'), oracleState.toBuffer(), reporter.publicKey.toBuffer()],
+      program.programId,
+    )
+    await program.methods
+      .depositTokens(new anchor.BN(1_000))
+      .accounts({
+        user: reporter.publicKey,
+        weightMint,
+        oracleState,
+        userTokenAccount: reporterToken.address,
+        oracleVault,
+        userState: reporterState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([reporter])
+      .rpc()
+
+    const [delegation] = PublicKey.findProgramAddressSync(
+      [Buffer.from('delegation'), oracleState.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+    const before = await program.account.userState.fetch(userState)
+
+    await program.methods
+      .delegate(reporter.publicKey)
+      .accounts({
+        user: wallet.publicKey,
+        oracleState,
+        userState,
+        delegateState: reporterState,
+        delegation,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc()
+
+    const delegated = await program.account.userState.fetch(reporterState)
+    expect(delegated.delegatedIn.eq(before.unlockedTokens)).toBe(true)
+    const delegator = await program.account.userState.fetch(userState)
+    expect(delegator.unlockedTokens.toNumber()).toBe(0)
+
+    await program.methods
+      .undelegate()
+      .accounts({
+        user: wallet.publicKey,
+        oracleState,
+        delegateState: reporterState,
+        delegation,
+      })
+      .rpc()
+
+    const released = await program.account.userState.fetch(reporterState)
+    expect(released.delegatedIn.toNumber()).toBe(0)
+
+    await program.methods
+      .completeUndelegation()
+      .accounts({
+        user: wallet.publicKey,
+        oracleState,
+        userState,
+        delegation,
+      })
+      .rpc()
+
+    const restored = await program.account.userState.fetch(userState)
+    expect(restored.unlockedTokens.eq(before.unlockedTokens)).toBe(true)
+  }, 60000)
+
+  it('lets a designated hot key submit for the staker', async () => {
+    const hotKey = anchor.web3.Keypair.generate()
+
+    await program.methods
+      .setSubmitter(hotKey.publicKey)
+      .accounts({
+        user: wallet.publicKey,
+        oracleState,
+        userState,
+      })
+      .rpc()
+
+    const configured = await program.account.userState.fetch(userState)
+    expect(configured.submitter.equals(hotKey.publicKey)).toBe(true)
+
+    await ensureOracleFunded(1_000_000)
+    const ownerBefore = await provider.connection.getBalance(wallet.publicKey)
+    const signature = await program.methods
+      .submitValue(submissionValue)
+      .accounts({
+        user: hotKey.publicKey,
+        oracleState,
+        userState,
+        owner: wallet.publicKey,
+      })
+      .signers([hotKey])
+      .rpc({ commitment: 'confirmed' })
+
+    const submitted = await program.account.userState.fetch(userState)
+    expect(submitted.lastSubmittedPrice.eq(submissionValue)).toBe(true)
+
+    // The reward goes to the owner, who also paid the fee; the hot key gets nothing.
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: 'confirmed',
+      maxSupportedTransactionVersion: 0,
+    })
+    const ownerAfter = await provider.connection.getBalance(wallet.publicKey)
+    expect(ownerAfter - ownerBefore + (tx?.meta?.fee ?? 0)).toBeGreaterThan(0)
+    expect(await provider.connection.getBalance(hotKey.publicKey)).toBe(0)
+  }, 60000)
+
+  it('stakes native SOL on a lamport-weighted oracle', async () => {
+    const [nativeOracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('native_oracle'), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+    const [nativeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from('native_vault'), nativeOracle.toBuffer()],
+      program.programId,
+    )
+    const [nativeUserState] = PublicKey.findProgramAddressSync(
+      [Buffer.from('user'), nativeOracle.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+
+    await program.methods
+      .initializeNative(defaultParams({
+        name: 'Native Test Oracle',
+        description: 'Oracle weighted by staked lamports',
+        rewardBps: new anchor.BN(500),
+        quorum: new anchor.BN(100),
+        treasury: wallet.publicKey,
+      }))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        oracleState: nativeOracle,
+        nativeVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc()
+
+    const vaultBefore = await provider.connection.getBalance(nativeVault)
+    const stake = new anchor.BN(50_000_000)
+
+    await program.methods
+      .depositNative(stake)
+      .accounts({
+        user: wallet.publicKey,
+        oracleState: nativeOracle,
+        nativeVault,
+        userState: nativeUserState,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc()
+
+    expect((await provider.connection.getBalance(nativeVault)) - vaultBefore).toBe(stake.toNumber())
+    const state = await program.account.oracleState.fetch(nativeOracle)
+    expect(state.totalDepositedTokens.eq(stake)).toBe(true)
+
+    await program.methods
+      .withdrawNative(stake)
+      .accounts({
+        user: wallet.publicKey,
+        oracleState: nativeOracle,
+        nativeVault,
+        userState: nativeUserState,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc()
+
+    expect(await provider.connection.getBalance(nativeVault)).toBe(vaultBefore)
+  }, 60000)
+
+  it('weights a collection oracle by escrowed NFTs', async () => {
+    // Mints and metadata are loaded by the local validator from tests/fixtures.
+    const collectionMint = new PublicKey('BjhzkXjfLnYPuzL7H2DVSaWWxne2VQJQg8TgiSJVLPUN')
+    const nftMint = new PublicKey('2i5GtPhk4w3ipg2NGWo1B74Liw3d1ETaZbad1NqnNABR')
+    const nftMetadata = new PublicKey('CEM5TH83NcBf8XhjT5n5vriVcjDrgE321YARXVQ2RBi4')
+    const holder = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(nftHolderSecret))
+
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(holder.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+    )
+    const holderNft = await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, nftMint, holder.publicKey)
+    await mintTo(provider.connection, wallet.payer, nftMint, holderNft.address, holder, 1)
+
+    const [nftOracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle'), wallet.publicKey.toBuffer(), collectionMint.toBuffer()],
+      program.programId,
+    )
+    const [holderState] = PublicKey.findProgramAddressSync(
+      [Buffer.from('user'), nftOracle.toBuffer(), holder.publicKey.toBuffer()],
+      program.programId,
+    )
+    const [nftEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from('nft_escrow'), nftOracle.toBuffer(), holder.publicKey.toBuffer(), nftMint.toBuffer()],
+      program.programId,
+    )
+    const [nftLock] = PublicKey.findProgramAddressSync(
+      [Buffer.from('nft_lock'), nftEscrow.toBuffer()],
+      program.programId,
+    )
+
+    await program.methods
+      .initializeNft(defaultParams({
+        name: 'Collection Test Oracle',
+        description: 'Oracle weighted by escrowed NFTs',
+        rewardBps: new anchor.BN(500),
+        quorum: new anchor.BN(1),
+        treasury: wallet.publicKey,
+      }))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        collectionMint,
+        oracleState: nftOracle,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc()
+
+    await program.methods
+      .depositNft()
+      .accounts({
+        user: holder.publicKey,
+        oracleState: nftOracle,
+        nftMint,
+        nftMetadata,
+        userNftAccount: holderNft.address,
+        nftEscrow,
+        nftLock,
+        userState: holderState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc()
+
+    const deposited = await program.account.userState.fetch(holderState)
+    expect(deposited.nftCount.toNumber()).toBe(1)
+    expect(Number((await getAccount(provider.connection, nftEscrow)).amount)).toBe(1)
+
+    await program.methods
+      .submitValue(submissionValue)
+      .accounts({
+        user: holder.publicKey,
+        oracleState: nftOracle,
+        userState: holderState,
+        owner: holder.publicKey,
+      })
+      .signers([holder])
+      .rpc()
+
+    const submitted = await program.account.userState.fetch(holderState)
+    expect(submitted.weight.toNumber()).toBe(1)
+
+    await program.methods
+      .withdrawNft()
+      .accounts({
+        user: holder.publicKey,
+        oracleState: nftOracle,
+        nftMint,
+        userNftAccount: holderNft.address,
+        nftEscrow,
+        nftLock,
+        userState: holderState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([holder])
+      .rpc()
+
+    const withdrawn = await program.account.userState.fetch(holderState)
+    expect(withdrawn.nftCount.toNumber()).toBe(0)
+    expect(Number((await getAccount(provider.connection, holderNft.address)).amount)).toBe(1)
+  }, 60000)
+
+  it('mints liquid receipts on deposit and burns them on withdrawal', async () => {
+    const receiptWeightMint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 6)
+    const stakerToken = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      receiptWeightMint,
+      wallet.publicKey,
+    )
+    await mintTo(provider.connection, wallet.payer, receiptWeightMint, stakerToken.address, wallet.publicKey, 5_000)
+
+    const [receiptOracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle'), wallet.publicKey.toBuffer(), receiptWeightMint.toBuffer()],
+      program.programId,
+    )
+    const receiptVault = await getAssociatedTokenAddress(receiptWeightMint, receiptOracle, true)
+    const [receiptUserState] = PublicKey.findProgramAddressSync(
+      [Buffer.from('user'), receiptOracle.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+    const [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from('receipt_mint'), receiptOracle.toBuffer()],
+      program.programId,
+    )
+
+    await program.methods
+      .initialize(defaultParams({
+        name: 'Receipt Test Oracle',
+        description: 'Oracle issuing liquid staking receipts',
+        rewardBps: new anchor.BN(500),
+        quorum: new anchor.BN(100),
+        treasury: stakerToken.address,
+      }))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        weightMint: receiptWeightMint,
+        oracleState: receiptOracle,
+        oracleVault: receiptVault,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc()
+
+    await program.methods
+      .createReceiptMint()
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        oracleState: receiptOracle,
+        weightMint: receiptWeightMint,
+        receiptMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+
+    const receiptAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      receiptMint,
+      wallet.publicKey,
+    )
+    const receiptAccounts = { receiptMint, userReceiptAccount: receiptAccount.address }
+
+    await program.methods
+      .depositTokens(new anchor.BN(5_000))
+      .accounts({
+        user: wallet.publicKey,
+        weightMint: receiptWeightMint,
+        oracleState: receiptOracle,
+        userTokenAccount: stakerToken.address,
+        oracleVault: receiptVault,
+        userState: receiptUserState,
+        ...receiptAccounts,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      } as never)
+      .rpc()
+
+    expect(Number((await getAccount(provider.connection, receiptAccount.address)).amount)).toBe(5_000)
+
+    await program.methods
+      .withdrawTokens(new anchor.BN(2_000))
+      .accounts({
+        user: wallet.publicKey,
+        weightMint: receiptWeightMint,
+        oracleState: receiptOracle,
+        userTokenAccount: stakerToken.address,
+        oracleVault: receiptVault,
+        userState: receiptUserState,
+        ...receiptAccounts,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as never)
+      .rpc()
+
+    expect(Number((await getAccount(provider.connection, receiptAccount.address)).amount)).toBe(3_000)
+
+    await expect(
+      program.methods
+        .freezeReceipts()
+        .accounts({
+          oracleState: receiptOracle,
+          receiptMint,
+          receiptAccount: receiptAccount.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        } as never)
+        .rpc(),
+    ).rejects.toThrow()
+  }, 60000)
+
+  it('only lets permitted stakers report on a permissioned oracle', async () => {
+    const feedMint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 6)
+    const stakerToken = await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, feedMint, wallet.publicKey)
+    await mintTo(provider.connection, wallet.payer, feedMint, stakerToken.address, wallet.publicKey, 5_000)
+
+    const [feedOracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle'), wallet.publicKey.toBuffer(), feedMint.toBuffer()],
+      program.programId,
+    )
+    const feedVault = await getAssociatedTokenAddress(feedMint, feedOracle, true)
+    const [feedUserState] = PublicKey.findProgramAddressSync(
+      [Buffer.from('user'), feedOracle.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+    const [submitterPermit] = PublicKey.findProgramAddressSync(
+      [Buffer.from('submitter'), feedOracle.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+
+    await program.methods
+      .initialize(defaultParams({
+        name: 'Permissioned Test Oracle',
+        description: 'Oracle reported by vetted publishers',
+        quorum: new anchor.BN(100),
+        treasury: stakerToken.address,
+        accessMode: { permissioned: {} },
+      }))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        weightMint: feedMint,
+        oracleState: feedOracle,
+        oracleVault: feedVault,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc()
+
+    await program.methods
+      .depositTokens(new anchor.BN(5_000))
+      .accounts({
+        user: wallet.publicKey,
+        weightMint: feedMint,
+        oracleState: feedOracle,
+        userTokenAccount: stakerToken.address,
+        oracleVault: feedVault,
+        userState: feedUserState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      } as never)
+      .rpc()
+
+    const submit = () =>
+      program.methods
+        .submitValue(submissionValue)
+        .accounts({
+          user: wallet.publicKey,
+          oracleState: feedOracle,
+          userState: feedUserState,
+          owner: wallet.publicKey,
+        } as never)
+        .rpc()
+
+    await expect(submit()).rejects.toThrow()
+
+    await program.methods
+      .grantSubmitter(wallet.publicKey)
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        oracleState: feedOracle,
+        submitterPermit,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+
+    await program.methods
+      .submitValue(submissionValue)
+      .accounts({
+        user: wallet.publicKey,
+        oracleState: feedOracle,
+        userState: feedUserState,
+        owner: wallet.publicKey,
+        submitterPermit,
+      } as never)
+      .rpc()
+
+    const state = await program.account.oracleState.fetch(feedOracle)
+    expect(state.latestValue.eq(submissionValue)).toBe(true)
+  }, 60000)
+
+  it('executes a stake-voted parameter proposal after the delay', async () => {
+    const govMint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 6)
+    const stakerToken = await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, govMint, wallet.publicKey)
+    await mintTo(provider.connection, wallet.payer, govMint, stakerToken.address, wallet.publicKey, 5_000)
+
+    const [govOracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle'), wallet.publicKey.toBuffer(), govMint.toBuffer()],
+      program.programId,
+    )
+    const govVault = await getAssociatedTokenAddress(govMint, govOracle, true)
+    const [govUserState] = PublicKey.findProgramAddressSync(
+      [Buffer.from('user'), govOracle.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+    const proposalId = new anchor.BN(0)
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), govOracle.toBuffer(), proposalId.toArrayLike(Buffer, 'le', 8)],
+      program.programId,
+    )
+    const [proposalVote] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal_vote'), proposal.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+
+    await program.methods
+      .initialize(defaultParams({
+        name: 'Governed Test Oracle',
+        description: 'Oracle parameters set by stake vote',
+        rewardBps: new anchor.BN(500),
+        treasury: stakerToken.address,
+        proposalVotingPeriod: new anchor.BN(2),
+        proposalExecutionGrace: new anchor.BN(3_600),
+      }))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        weightMint: govMint,
+        oracleState: govOracle,
+        oracleVault: govVault,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc()
+
+    await program.methods
+      .depositTokens(new anchor.BN(5_000))
+      .accounts({
+        user: wallet.publicKey,
+        weightMint: govMint,
+        oracleState: govOracle,
+        userTokenAccount: stakerToken.address,
+        oracleVault: govVault,
+        userState: govUserState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      } as never)
+      .rpc()
+
+    // Votes count weight held before the slot the proposal opens in.
+    await nextSlot()
+
+    await program.methods
+      .createProposal(proposalId, { rewardBps: new anchor.BN(1_000), resetCircuitBreaker: false } as never)
+      .accounts({
+        proposer: wallet.publicKey,
+        oracleState: govOracle,
+        userState: govUserState,
+        proposal,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+
+    await program.methods
+      .voteProposal(true)
+      .accounts({
+        voter: wallet.publicKey,
+        oracleState: govOracle,
+        userState: govUserState,
+        proposal,
+        proposalVote,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+
+    const execute = () =>
+      program.methods.executeProposal().accounts({ oracleState: govOracle, proposal } as never).rpc()
+
+    await expect(execute()).rejects.toThrow()
+    await new Promise((resolve) => setTimeout(resolve, 3000))
+    await execute()
+
+    const state = await program.account.oracleState.fetch(govOracle)
+    expect(state.rewardBps.toNumber()).toBe(1_000)
+    expect((await program.account.proposal.fetch(proposal)).executed).toBe(true)
+  }, 60000)
+
+  it('forfeits the bond of a rejected blacklist appeal', async () => {
+    const appellant = anchor.web3.Keypair.generate()
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(appellant.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+    )
+    const appealMint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 6)
+    const stakerToken = await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, appealMint, wallet.publicKey)
+    await mintTo(provider.connection, wallet.payer, appealMint, stakerToken.address, wallet.publicKey, 5_000)
+
+    const [appealOracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle'), wallet.publicKey.toBuffer(), appealMint.toBuffer()],
+      program.programId,
+    )
+    const appealVault = await getAssociatedTokenAddress(appealMint, appealOracle, true)
+    const [appealUserState] = PublicKey.findProgramAddressSync(
+      [Buffer.from('user'), appealOracle.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId,
+    )
+    const [appeal] = PublicKey.findProgramAddressSync(
+      [Buffer.from('appeal'), appealOracle.toBuffer(), appellant.publicKey.toBuffer()],
+      program.programId,
+    )
+    const bond = 100_000_000
+
+    await program.methods
+      .initialize(defaultParams({
+        name: 'Appeal Test Oracle',
+        description: 'Oracle with bonded blacklist appeals',
+        treasury: stakerToken.address,
+        appealBond: new anchor.BN(bond),
+        appealVotingPeriod: new anchor.BN(2),
+      }))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        weightMint: appealMint,
+        oracleState: appealOracle,
+        oracleVault: appealVault,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc()
+
+    await program.methods
+      .depositTokens(new anchor.BN(5_000))
+      .accounts({
+        user: wallet.publicKey,
+        weightMint: appealMint,
+        oracleState: appealOracle,
+        userTokenAccount: stakerToken.address,
+        oracleVault: appealVault,
+        userState: appealUserState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      } as never)
+      .rpc()
+
+    await nextSlot()
+    await program.methods
+      .voteBlacklist(appellant.publicKey)
+      .accounts({ user: wallet.publicKey, oracleState: appealOracle, userState: appealUserState } as never)
+      .rpc()
+
+    await program.methods
+      .openAppeal()
+      .accounts({
+        appellant: appellant.publicKey,
+        oracleState: appealOracle,
+        appeal,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .signers([appellant])
+      .rpc()
+
+    const poolBefore = await provider.connection.getBalance(appealOracle)
+    await new Promise((resolve) => setTimeout(resolve, 3000))
+
+    await program.methods
+      .resolveAppeal()
+      .accounts({ oracleState: appealOracle, appeal, appellant: appellant.publicKey } as never)
+      .rpc()
+
+    expect((await provider.connection.getBalance(appealOracle)) - poolBefore).toBe(bond)
+    expect(await provider.connection.getAccountInfo(appeal)).toBeNull()
+    const state = await program.account.oracleState.fetch(appealOracle)
+    const record = state.targets.find((target) => target.target.toBase58() === appellant.publicKey.toBase58())
+    expect(record?.isBlacklisted).toBe(true)
+  }, 60000)
+
+  it('re-evaluates a percentage quorum as deposits change', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Quorum Test Oracle',
+      description: 'Oracle with a quorum relative to vote weight',
+      quorumBps: new anchor.BN(50_000),
+    })
+    const voter = wallet.payer
+    const whale = await fundedKeypair()
+    const target = anchor.web3.Keypair.generate().publicKey
+    const isBlacklisted = async () =>
+      (await feed.fetchState()).targets.find((record) => record.target.equals(target))?.isBlacklisted
+    const refresh = () =>
+      program.methods.refreshTarget(target).accounts({ oracleState: feed.oracle } as never).rpc()
+
+    await feed.deposit(voter, 5_000)
+    expect((await feed.fetchState()).totalVoteWeight.toNumber()).toBe(5_000)
+    await nextSlot()
+    await program.methods
+      .voteBlacklist(target)
+      .accounts({ user: voter.publicKey, oracleState: feed.oracle, userState: feed.userStateOf(voter.publicKey) } as never)
+      .rpc()
+    // 5_000 votes against a quorum of half of 5_000.
+    expect(await isBlacklisted()).toBe(true)
+
+    await feed.deposit(whale, 20_000)
+    expect((await feed.fetchState()).totalVoteWeight.toNumber()).toBe(25_000)
+    // Weight moved in the current slot does not count towards quorum yet.
+    await nextSlot()
+    await refresh()
+    // The quorum is now half of 25_000.
+    expect(await isBlacklisted()).toBe(false)
+
+    await feed.withdraw(whale, 20_000)
+    expect((await feed.fetchState()).totalVoteWeight.toNumber()).toBe(5_000)
+    await nextSlot()
+    await refresh()
+    expect(await isBlacklisted()).toBe(true)
+  }, 60000)
+
+  it('slashes the submitter of an upheld dispute and records the outcome', async () => {
+    const voter = anchor.web3.Keypair.generate()
+    const disputer = anchor.web3.Keypair.generate()
+    for (const keypair of [voter, disputer]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(keypair.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+      )
+    }
+    const disputeMint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 6)
+    const submitterToken = await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, disputeMint, wallet.publicKey)
+    const voterToken = await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, disputeMint, voter.publicKey)
+    const disputerToken = await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, disputeMint, disputer.publicKey)
+    await mintTo(provider.connection, wallet.payer, disputeMint, submitterToken.address, wallet.publicKey, 5_000)
+    await mintTo(provider.connection, wallet.payer, disputeMint, voterToken.address, wallet.publicKey, 20_000)
+
+    const [disputeOracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle'), wallet.publicKey.toBuffer(), disputeMint.toBuffer()],
+      program.programId,
+    )
+    const disputeVault = await getAssociatedTokenAddress(disputeMint, disputeOracle, true)
+    const userStateOf = (owner: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from('user'), disputeOracle.toBuffer(), owner.toBuffer()],
+        program.programId,
+      )[0]
+
+    await program.methods
+      .initialize(defaultParams({
+        name: 'Dispute Test Oracle',
+        description: 'Oracle whose reports can be challenged',
+        quorum: new anchor.BN(1),
+        treasury: submitterToken.address,
+        disputeBond: new anchor.BN(1_000_000),
+        disputeVotingPeriod: new anchor.BN(2),
+        disputeSlashBps: new anchor.BN(50_000),
+      }))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        weightMint: disputeMint,
+        oracleState: disputeOracle,
+        oracleVault: disputeVault,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc()
+
+    const stakeAccounts = (owner: PublicKey, userTokenAccount: PublicKey) => ({
+      user: owner,
+      weightMint: disputeMint,
+      oracleState: disputeOracle,
+      userTokenAccount,
+      oracleVault: disputeVault,
+      userState: userStateOf(owner),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    await program.methods
+      .depositTokens(new anchor.BN(5_000))
+      .accounts(stakeAccounts(wallet.publicKey, submitterToken.address) as never)
+      .rpc()
+    await program.methods
+      .depositTokens(new anchor.BN(20_000))
+      .accounts(stakeAccounts(voter.publicKey, voterToken.address) as never)
+      .signers([voter])
+      .rpc()
+    await program.methods
+      .submitValue(new anchor.BN(100))
+      .accounts({
+        user: wallet.publicKey,
+        oracleState: disputeOracle,
+        userState: userStateOf(wallet.publicKey),
+        owner: wallet.publicKey,
+      } as never)
+      .rpc()
+
+    const submitted = (await program.account.userState.fetch(userStateOf(wallet.publicKey))).recentSubmissions[0]
+    const [dispute] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('dispute'),
+        disputeOracle.toBuffer(),
+        wallet.publicKey.toBuffer(),
+        submitted.sequence.toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    )
+    // The vote snapshot must fall after the voter's deposit.
+    await nextSlot()
+    await program.methods
+      .openDispute(submitted.sequence, new anchor.BN(90))
+      .accounts({
+        disputer: disputer.publicKey,
+        oracleState: disputeOracle,
+        submitterState: userStateOf(wallet.publicKey),
+        dispute,
+      } as never)
+      .signers([disputer])
+      .rpc()
+
+    await expect(
+      program.methods
+        .withdrawTokens(new anchor.BN(1_000))
+        .accounts(stakeAccounts(wallet.publicKey, submitterToken.address) as never)
+        .rpc(),
+    ).rejects.toThrow()
+
+    await program.methods
+      .voteDispute(true)
+      .accounts({
+        voter: voter.publicKey,
+        oracleState: disputeOracle,
+        userState: userStateOf(voter.publicKey),
+        dispute,
+      } as never)
+      .signers([voter])
+      .rpc()
+
+    await new Promise((resolve) => setTimeout(resolve, 3000))
+    await program.methods
+      .resolveDispute()
+      .accounts({
+        oracleState: disputeOracle,
+        dispute,
+        disputer: disputer.publicKey,
+        submitterState: userStateOf(wallet.publicKey),
+        weightMint: disputeMint,
+        oracleVault: disputeVault,
+        disputerTokenAccount: disputerToken.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        nativeVault: null,
+        submitterDelegation: null,
+        delegateState: null,
+      } as never)
+      .rpc()
+
+    expect(Number((await getAccount(provider.connection, disputerToken.address)).amount)).toBe(2_500)
+    expect(await provider.connection.getAccountInfo(dispute)).toBeNull()
+    const submitter = await program.account.userState.fetch(userStateOf(wallet.publicKey))
+    expect(submitter.unlockedTokens.add(submitter.lockedTokens).toNumber()).toBe(2_500)
+    expect(submitter.openDisputes.toNumber()).toBe(0)
+    const history = await program.account.priceHistory.fetch(historyOf(disputeOracle))
+    const record = history.records.find((entry) => entry.sequence.eq(submitted.sequence))
+    expect(record?.dispute).toEqual({ upheld: {} })
+  }, 60000)
+
+  it('halts on per-update and windowed deviation and resumes after a reset', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Breaker Test Oracle',
+      description: 'Oracle with deviation limits',
+      maxUpdateDeviationBps: new anchor.BN(10_000),
+      maxWindowDeviationBps: new anchor.BN(15_000),
+      deviationWindowSeconds: new anchor.BN(3_600),
+    })
+    const reporter = wallet.payer
+    const reset = () =>
+      program.methods
+        .resetCircuitBreaker()
+        .accounts({ authority: wallet.publicKey, oracleState: feed.oracle } as never)
+        .rpc()
+    await feed.deposit(reporter, 1_000)
+
+    await feed.submit(reporter, 100_000)
+    // 15% above the last aggregate against a 10% per-update limit.
+    await feed.submit(reporter, 115_000)
+    let state = await feed.fetchState()
+    expect(state.status).toEqual({ halted: {} })
+    expect(state.aggregatedValue.toNumber()).toBe(100_000)
+    await expect(feed.submit(reporter, 100_000)).rejects.toThrow(/OracleHalted/)
+
+    // History from before the reset no longer anchors the limits.
+    await sleep(1500)
+    await reset()
+    await feed.submit(reporter, 115_000)
+    expect((await feed.fetchState()).aggregatedValue.toNumber()).toBe(115_000)
+
+    // Each step stays within 10% of the previous aggregate, but the second
+    // drifts more than 15% from 115_000 within the window.
+    await feed.submit(reporter, 105_000)
+    await feed.submit(reporter, 96_000)
+    state = await feed.fetchState()
+    expect(state.status).toEqual({ halted: {} })
+    expect(state.aggregatedValue.toNumber()).toBe(105_000)
+
+    await sleep(1500)
+    await reset()
+    await feed.submit(reporter, 96_000)
+    state = await feed.fetchState()
+    expect(state.status).toEqual({ active: {} })
+    expect(state.aggregatedValue.toNumber()).toBe(96_000)
+  }, 60000)
+
+  it('unlocks each deposit lot on its own schedule', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Lot Test Oracle',
+      description: 'Oracle with a deposit lock',
+      depositLockingPeriod: new anchor.BN(4),
+    })
+    const staker = wallet.payer
+    const refresh = () =>
+      program.methods
+        .updateUserVoteWeights()
+        .accounts({ caller: staker.publicKey, oracleState: feed.oracle, userState: feed.userStateOf(staker.publicKey) } as never)
+        .rpc()
+    const fetchUser = () => program.account.userState.fetch(feed.userStateOf(staker.publicKey))
+
+    await feed.deposit(staker, 100)
+    await sleep(2500)
+    await feed.deposit(staker, 200)
+    let user = await fetchUser()
+    expect(user.depositLots.length).toBe(2)
+    expect(user.lockedTokens.toNumber()).toBe(300)
+
+    // The first lot has matured, the second is still locked.
+    await sleep(2000)
+    await refresh()
+    user = await fetchUser()
+    expect(user.unlockedTokens.toNumber()).toBe(100)
+    expect(user.lockedTokens.toNumber()).toBe(200)
+    expect(user.depositLots.length).toBe(1)
+    await expect(feed.withdraw(staker, 101)).rejects.toThrow()
+
+    await sleep(3000)
+    await refresh()
+    user = await fetchUser()
+    expect(user.unlockedTokens.toNumber()).toBe(300)
+    expect(user.lockedTokens.toNumber()).toBe(0)
+    expect(user.depositLots.length).toBe(0)
+  }, 60000)
+
+  it('lets an active reporter leave through the unbonding queue', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Unbonding Test Oracle',
+      description: 'Oracle with a withdrawal locking period',
+      withdrawalLockingPeriod: new anchor.BN(2),
+    })
+    const reporter = wallet.payer
+    await feed.deposit(reporter, 1_000)
+    await feed.submit(reporter, 100)
+
+    await expect(feed.withdraw(reporter, 1_000)).rejects.toThrow(/UnbondingRequired/)
+
+    await program.methods
+      .requestWithdrawal(new anchor.BN(1_000))
+      .accounts({ user: reporter.publicKey, oracleState: feed.oracle, userState: feed.userStateOf(reporter.publicKey) } as never)
+      .rpc()
+    await expect(feed.submit(reporter, 100)).rejects.toThrow(/NoUnlockedTokens/)
+
+    await sleep(3000)
+    const tokenAccount = await feed.tokenAccountOf(reporter.publicKey)
+    const before = await getAccount(provider.connection, tokenAccount)
+    await program.methods
+      .completeWithdrawal()
+      .accounts((await feed.stakeAccounts(reporter)) as never)
+      .rpc()
+    const after = await getAccount(provider.connection, tokenAccount)
+    expect(Number(after.amount) - Number(before.amount)).toBe(1_000)
+  }, 60000)
+
+  it('rejects early withdrawal when the penalty is zero', async () => {
+    const mint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 6)
+    const treasury = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, mint, anchor.web3.Keypair.generate().publicKey)
+    ).address
+    const feed = await setupTokenOracle(
+      {
+        name: 'No Early Exit Oracle',
+        description: 'Oracle without an early-exit penalty',
+        depositLockingPeriod: new anchor.BN(3_600),
+        treasury,
+      },
+      mint,
+    )
+    const staker = wallet.payer
+    await feed.deposit(staker, 1_000)
+
+    await expect(
+      program.methods
+        .withdrawEarly(new anchor.BN(1_000))
+        .accounts({ ...(await feed.stakeAccounts(staker)), treasuryTokenAccount: treasury } as never)
+        .rpc(),
+    ).rejects.toThrow(/EarlyExitDisabled/)
+  }, 60000)
+
+  it('rejects a vote-escrow boost above the maximum', async () => {
+    await expect(
+      setupTokenOracle({
+        name: 'Oversized Boost Oracle',
+        description: 'Oracle with an unbounded vote-escrow boost',
+        veMaxLockSeconds: new anchor.BN(3_600),
+        veMaxBoostBps: new anchor.BN(400_001),
+      }),
+    ).rejects.toThrow(/InvalidVeBoost/)
+  }, 60000)
+
+  it('caps a whale submission at max_user_weight', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Absolute Cap Oracle',
+      description: 'Oracle with an absolute per-user weight cap',
+      maxUserWeight: new anchor.BN(500),
+    })
+    const whale = wallet.payer
+    await feed.deposit(whale, 10_000)
+    await feed.submit(whale, 42)
+
+    const history = await program.account.priceHistory.fetch(historyOf(feed.oracle))
+    expect(history.records[history.records.length - 1].weight.toNumber()).toBe(500)
+  }, 60000)
+
+  it('caps a whale submission at max_user_weight_bps of total stake', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Share Cap Oracle',
+      description: 'Oracle with a share-of-stake weight cap',
+      maxUserWeightBps: new anchor.BN(25_000),
+    })
+    const whale = wallet.payer
+    const minnow = await fundedKeypair()
+    await feed.deposit(whale, 3_000)
+    await feed.deposit(minnow, 1_000)
+    await feed.submit(whale, 42)
+
+    // 25% of the 4_000 staked in total.
+    const history = await program.account.priceHistory.fetch(historyOf(feed.oracle))
+    expect(history.records[history.records.length - 1].weight.toNumber()).toBe(1_000)
+  }, 60000)
+
+  it('weights submissions by the square root of stake', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Square Root Oracle',
+      description: 'Oracle weighting stake by its square root',
+      weightingMode: { squareRoot: {} },
+    })
+    const staker = wallet.payer
+    await feed.deposit(staker, 10_000)
+    await feed.submit(staker, 42)
+
+    const history = await program.account.priceHistory.fetch(historyOf(feed.oracle))
+    expect(history.records[history.records.length - 1].weight.toNumber()).toBe(100)
+  }, 60000)
+
+  it('rejects submissions and votes from stakes below the minimums', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Minimum Stake Oracle',
+      description: 'Oracle with minimum submit and vote stakes',
+      minSubmitStake: new anchor.BN(1_000),
+      minVoteStake: new anchor.BN(1_000),
+    })
+    const staker = wallet.payer
+    await feed.deposit(staker, 999)
+    // The vote weight at the new target's snapshot is below the minimum.
+    await nextSlot()
+
+    await expect(feed.submit(staker, 42)).rejects.toThrow(/StakeBelowSubmitMinimum/)
+    await expect(
+      program.methods
+        .voteBlacklist(anchor.web3.Keypair.generate().publicKey)
+        .accounts({ user: staker.publicKey, oracleState: feed.oracle, userState: feed.userStateOf(staker.publicKey) } as never)
+        .rpc(),
+    ).rejects.toThrow(/StakeBelowVoteMinimum/)
+
+    await feed.deposit(staker, 1)
+    await feed.submit(staker, 42)
+  }, 60000)
+
+  it('rejects a second submission inside the minimum interval', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Rate Limited Oracle',
+      description: 'Oracle with a minimum submission interval',
+      minSubmissionInterval: new anchor.BN(3_600),
+    })
+    const staker = wallet.payer
+    await feed.deposit(staker, 1_000)
+    await feed.submit(staker, 42)
+
+    await expect(feed.submit(staker, 43)).rejects.toThrow(/SubmissionTooFrequent/)
+  }, 60000)
+
+  it('stakes a Token-2022 weight mint', async () => {
+    const mint = await createToken2022Mint([], () => [])
+    const feed = await setupTokenOracle(
+      { name: 'Token-2022 Oracle', description: 'Oracle weighted by a Token-2022 mint' },
+      mint,
+      TOKEN_2022_PROGRAM_ID,
+    )
+    const staker = wallet.payer
+    await feed.deposit(staker, 1_000)
+    expect((await program.account.userState.fetch(feed.userStateOf(staker.publicKey))).unlockedTokens.toNumber()).toBe(1_000)
+
+    await feed.withdraw(staker, 1_000)
+    const tokenAccount = await feed.tokenAccountOf(staker.publicKey)
+    expect(Number((await getAccount(provider.connection, tokenAccount, undefined, TOKEN_2022_PROGRAM_ID)).amount)).toBe(1_000)
+  }, 60000)
+
+  it('credits only what reaches the vault under a transfer fee', async () => {
+    // 1% fee, uncapped.
+    const mint = await createToken2022Mint([ExtensionType.TransferFeeConfig], (mint) => [
+      createInitializeTransferFeeConfigInstruction(
+        mint,
+        wallet.publicKey,
+        wallet.publicKey,
+        100,
+        BigInt(1_000_000),
+        TOKEN_2022_PROGRAM_ID,
+      ),
+    ])
+    const feed = await setupTokenOracle(
+      { name: 'Transfer Fee Oracle', description: 'Oracle weighted by a fee-bearing mint' },
+      mint,
+      TOKEN_2022_PROGRAM_ID,
+    )
+    const staker = wallet.payer
+    await feed.deposit(staker, 10_000)
+
+    const user = await program.account.userState.fetch(feed.userStateOf(staker.publicKey))
+    expect(user.unlockedTokens.toNumber()).toBe(9_900)
+    expect((await feed.fetchState()).totalDepositedTokens.toNumber()).toBe(9_900)
+  }, 60000)
+
+  it('rejects Token-2022 mints with unsupported extensions', async () => {
+    const unsupported = [
+      await createToken2022Mint([ExtensionType.NonTransferable], (mint) => [
+        createInitializeNonTransferableMintInstruction(mint, TOKEN_2022_PROGRAM_ID),
+      ]),
+      await createToken2022Mint([ExtensionType.PermanentDelegate], (mint) => [
+        createInitializePermanentDelegateInstruction(mint, wallet.publicKey, TOKEN_2022_PROGRAM_ID),
+      ]),
+      await createToken2022Mint([ExtensionType.TransferHook], (mint) => [
+        createInitializeTransferHookInstruction(
+          mint,
+          wallet.publicKey,
+          anchor.web3.Keypair.generate().publicKey,
+          TOKEN_2022_PROGRAM_ID,
+        ),
+      ]),
+      await createToken2022Mint([ExtensionType.DefaultAccountState], (mint) => [
+        createInitializeDefaultAccountStateInstruction(mint, AccountState.Frozen, TOKEN_2022_PROGRAM_ID),
+      ]),
+    ]
+
+    for (const mint of unsupported) {
+      await expect(
+        setupTokenOracle(
+          { name: 'Unsupported Mint Oracle', description: 'Oracle weighted by an unsupported mint' },
+          mint,
+          TOKEN_2022_PROGRAM_ID,
+        ),
+      ).rejects.toThrow(/UnsupportedMintExtension/)
+    }
+  }, 60000)
+
+  it('lets a blacklisted staker withdraw past frozen receipts', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Frozen Receipt Oracle',
+      description: 'Oracle freezing a blacklisted staker receipts',
+      quorum: new anchor.BN(100),
+    })
+    const [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from('receipt_mint'), feed.oracle.toBuffer()],
+      program.programId,
+    )
+    await program.methods
+      .createReceiptMint()
+      .accounts({
+        payer: wallet.publicKey,
+        authority: wallet.publicKey,
+        oracleState: feed.oracle,
+        weightMint: feed.mint,
+        receiptMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+
+    const voter = wallet.payer
+    const staker = await fundedKeypair()
+    const receiptAccountOf = async (owner: PublicKey) =>
+      (await getOrCreateAssociatedTokenAccount(provider.connection, wallet.payer, receiptMint, owner)).address
+    const stakeWithReceipts = async (owner: anchor.web3.Keypair) => ({
+      ...(await feed.stakeAccounts(owner)),
+      receiptMint,
+      userReceiptAccount: await receiptAccountOf(owner.publicKey),
+    })
+    const stakerReceipts = await receiptAccountOf(staker.publicKey)
+
+    for (const owner of [staker, voter]) {
+      await mintTo(provider.connection, wallet.payer, feed.mint, await feed.tokenAccountOf(owner.publicKey), wallet.publicKey, 1_000)
+      await program.methods
+        .depositTokens(new anchor.BN(1_000))
+        .accounts((await stakeWithReceipts(owner)) as never)
+        .signers([owner])
+        .rpc()
+    }
+    await nextSlot()
+    await program.methods
+      .voteBlacklist(staker.publicKey)
+      .accounts({ user: voter.publicKey, oracleState: feed.oracle, userState: feed.userStateOf(voter.publicKey) } as never)
+      .rpc()
+    await program.methods
+      .freezeReceipts()
+      .accounts({
+        oracleState: feed.oracle,
+        receiptMint,
+        receiptAccount: stakerReceipts,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as never)
+      .rpc()
+    expect((await getAccount(provider.connection, stakerReceipts)).isFrozen).toBe(true)
+
+    await program.methods
+      .withdrawTokens(new anchor.BN(1_000))
+      .accounts((await stakeWithReceipts(staker)) as never)
+      .signers([staker])
+      .rpc()
+
+    const receipts = await getAccount(provider.connection, stakerReceipts)
+    expect(Number(receipts.amount)).toBe(0)
+    expect(receipts.isFrozen).toBe(true)
+    const tokens = await getAccount(provider.connection, await feed.tokenAccountOf(staker.publicKey))
+    expect(Number(tokens.amount)).toBe(1_000)
+  }, 60000)
+
+  it('rejects executing a proposal after its grace period', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Expiring Proposal Oracle',
+      description: 'Oracle whose proposals lapse',
+      proposalVotingPeriod: new anchor.BN(2),
+      proposalExecutionGrace: new anchor.BN(1),
+    })
+    const staker = wallet.payer
+    await feed.deposit(staker, 5_000)
+    // Votes count weight held before the slot the proposal opens in.
+    await nextSlot()
+
+    const proposalId = new anchor.BN(0)
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), feed.oracle.toBuffer(), proposalId.toArrayLike(Buffer, 'le', 8)],
+      program.programId,
+    )
+    const [proposalVote] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal_vote'), proposal.toBuffer(), staker.publicKey.toBuffer()],
+      program.programId,
+    )
+    await program.methods
+      .createProposal(proposalId, { rewardBps: new anchor.BN(1_000), resetCircuitBreaker: false } as never)
+      .accounts({
+        proposer: staker.publicKey,
+        oracleState: feed.oracle,
+        userState: feed.userStateOf(staker.publicKey),
+        proposal,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+    await program.methods
+      .voteProposal(true)
+      .accounts({
+        voter: staker.publicKey,
+        oracleState: feed.oracle,
+        userState: feed.userStateOf(staker.publicKey),
+        proposal,
+        proposalVote,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+
+    // Voting closes after 2s and the proposal lapses 1s later.
+    await sleep(5000)
+    await expect(
+      program.methods.executeProposal().accounts({ oracleState: feed.oracle, proposal } as never).rpc(),
+    ).rejects.toThrow(/ProposalExpired/)
+    expect((await feed.fetchState()).rewardBps.toNumber()).toBe(0)
+  }, 60000)
+
+  it('requires an execution grace period when governance is enabled', async () => {
+    await expect(
+      setupTokenOracle({
+        name: 'Graceless Governance Oracle',
+        description: 'Oracle with proposals that could never run',
+        proposalVotingPeriod: new anchor.BN(60),
+      }),
+    ).rejects.toThrow(/InvalidExecutionGrace/)
+  }, 60000)
+
+  it('voids blacklist votes pardoned by a granted appeal', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Pardon Oracle',
+      description: 'Oracle granting a blacklist appeal',
+      quorum: new anchor.BN(100),
+      appealVotingPeriod: new anchor.BN(2),
+    })
+    const accuser = wallet.payer
+    const supporter = await fundedKeypair()
+    const appellant = await fundedKeypair()
+    await feed.deposit(accuser, 1_000)
+    await feed.deposit(supporter, 2_000)
+
+    const voteBlacklist = () =>
+      program.methods
+        .voteBlacklist(appellant.publicKey)
+        .accounts({ user: accuser.publicKey, oracleState: feed.oracle, userState: feed.userStateOf(accuser.publicKey) } as never)
+        .rpc()
+    const record = async () =>
+      (await feed.fetchState()).targets.find((target) => target.target.equals(appellant.publicKey))
+    await nextSlot()
+    await voteBlacklist()
+    expect((await record())?.isBlacklisted).toBe(true)
+    const [appeal] = PublicKey.findProgramAddressSync(
+      [Buffer.from('appeal'), feed.oracle.toBuffer(), appellant.publicKey.toBuffer()],
+      program.programId,
+    )
+    const [appealVote] = PublicKey.findProgramAddressSync(
+      [Buffer.from('appeal_vote'), appeal.toBuffer(), supporter.publicKey.toBuffer()],
+      program.programId,
+    )
+    await program.methods
+      .openAppeal()
+      .accounts({ appellant: appellant.publicKey, oracleState: feed.oracle, appeal, systemProgram: SystemProgram.programId } as never)
+      .signers([appellant])
+      .rpc()
+    await program.methods
+      .voteAppeal(true)
+      .accounts({
+        voter: supporter.publicKey,
+        oracleState: feed.oracle,
+        userState: feed.userStateOf(supporter.publicKey),
+        appeal,
+        appealVote,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .signers([supporter])
+      .rpc()
+    await sleep(3000)
+    await program.methods
+      .resolveAppeal()
+      .accounts({ oracleState: feed.oracle, appeal, appellant: appellant.publicKey } as never)
+      .rpc()
+
+    const pardoned = await record()
+    expect(pardoned?.isBlacklisted).toBe(false)
+    expect(pardoned?.blacklistVotes.toNumber()).toBe(0)
+
+    // A fresh vote counts again, weighed as of the pardon, so stake added
+    // since then is left out.
+    await feed.deposit(accuser, 500)
+    expect((await record())?.blacklistVotes.toNumber()).toBe(0)
+    await voteBlacklist()
+    const reblacklisted = await record()
+    expect(reblacklisted?.blacklistVotes.toNumber()).toBe(1_000)
+    expect(reblacklisted?.isBlacklisted).toBe(true)
+  }, 60000)
+
+  it('ignores stake moved around a refresh in the same transaction', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Flash Quorum Oracle',
+      description: 'Oracle with a quorum relative to vote weight',
+      quorumBps: new anchor.BN(50_000),
+    })
+    const voter = wallet.payer
+    const whale = await fundedKeypair()
+    const target = anchor.web3.Keypair.generate().publicKey
+    await feed.deposit(voter, 5_000)
+    await nextSlot()
+    await program.methods
+      .voteBlacklist(target)
+      .accounts({ user: voter.publicKey, oracleState: feed.oracle, userState: feed.userStateOf(voter.publicKey) } as never)
+      .rpc()
+    expect((await feed.fetchState()).targets.find((entry) => entry.target.equals(target))?.isBlacklisted).toBe(true)
+
+    // Borrowed stake would lift the quorum to half of 25_000 if it counted at once.
+    const whaleToken = await feed.tokenAccountOf(whale.publicKey)
+    await mintTo(provider.connection, wallet.payer, feed.mint, whaleToken, wallet.publicKey, 20_000)
+    const whaleAccounts = (await feed.stakeAccounts(whale)) as never
+    const transaction = new anchor.web3.Transaction().add(
+      await program.methods.depositTokens(new anchor.BN(20_000)).accounts(whaleAccounts).instruction(),
+      await program.methods.refreshTarget(target).accounts({ oracleState: feed.oracle } as never).instruction(),
+      await program.methods.withdrawTokens(new anchor.BN(20_000)).accounts(whaleAccounts).instruction(),
+    )
+    await provider.sendAndConfirm(transaction, [whale])
+
+    const record = (await feed.fetchState()).targets.find((entry) => entry.target.equals(target))
+    expect(record?.isBlacklisted).toBe(true)
+  }, 60000)
+
+  it('gives no proposal vote to stake deposited after the snapshot', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Snapshot Vote Oracle',
+      description: 'Oracle weighting proposal votes by earlier stake',
+      proposalVotingPeriod: new anchor.BN(60),
+      proposalExecutionGrace: new anchor.BN(3_600),
+    })
+    const proposer = wallet.payer
+    const latecomer = await fundedKeypair()
+    await feed.deposit(proposer, 5_000)
+    await nextSlot()
+
+    const proposalId = new anchor.BN(0)
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), feed.oracle.toBuffer(), proposalId.toArrayLike(Buffer, 'le', 8)],
+      program.programId,
+    )
+    await program.methods
+      .createProposal(proposalId, { rewardBps: new anchor.BN(1_000), resetCircuitBreaker: false } as never)
+      .accounts({
+        proposer: proposer.publicKey,
+        oracleState: feed.oracle,
+        userState: feed.userStateOf(proposer.publicKey),
+        proposal,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+    await feed.deposit(latecomer, 50_000)
+
+    const [proposalVote] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal_vote'), proposal.toBuffer(), latecomer.publicKey.toBuffer()],
+      program.programId,
+    )
+    await expect(
+      program.methods
+        .voteProposal(true)
+        .accounts({
+          voter: latecomer.publicKey,
+          oracleState: feed.oracle,
+          userState: feed.userStateOf(latecomer.publicKey),
+          proposal,
+          proposalVote,
+          systemProgram: SystemProgram.programId,
+        } as never)
+        .signers([latecomer])
+        .rpc(),
+    ).rejects.toThrow(/NoStakeAtSnapshot/)
+  }, 60000)
+
+  it('requires a dispute bond and keeps disputes apart from receipts', async () => {
+    await expect(
+      setupTokenOracle({
+        name: 'Free Dispute Oracle',
+        description: 'Oracle with unbonded disputes',
+        disputeVotingPeriod: new anchor.BN(60),
+      }),
+    ).rejects.toThrow(/InvalidDisputeBond/)
+
+    const feed = await setupTokenOracle({
+      name: 'Disputed Receipt Oracle',
+      description: 'Oracle with disputes enabled',
+      disputeBond: new anchor.BN(1_000_000),
+      disputeVotingPeriod: new anchor.BN(60),
+    })
+    const [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from('receipt_mint'), feed.oracle.toBuffer()],
+      program.programId,
+    )
+    await expect(
+      program.methods
+        .createReceiptMint()
+        .accounts({
+          payer: wallet.publicKey,
+          authority: wallet.publicKey,
+          oracleState: feed.oracle,
+          weightMint: feed.mint,
+          receiptMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as never)
+        .rpc(),
+    ).rejects.toThrow(/ReceiptsWithDisputes/)
+  }, 60000)
+
+  it('keeps an escrowed NFT until its own deposit lock ends', async () => {
+    const collectionMint = new PublicKey('BjhzkXjfLnYPuzL7H2DVSaWWxne2VQJQg8TgiSJVLPUN')
+    const nftMint = new PublicKey('2i5GtPhk4w3ipg2NGWo1B74Liw3d1ETaZbad1NqnNABR')
+    const nftMetadata = new PublicKey('CEM5TH83NcBf8XhjT5n5vriVcjDrgE321YARXVQ2RBi4')
+    const holder = anchor.web3.Keypair.fromSecretKey(Uint8Array.from(nftHolderSecret))
+    // Collection oracles are keyed by authority and collection, so this one
+    // needs an authority of its own.
+    const authority = await fundedKeypair()
+    const holderNft = await getAssociatedTokenAddress(nftMint, holder.publicKey)
+
+    const [nftOracle] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle'), authority.publicKey.toBuffer(), collectionMint.toBuffer()],
+      program.programId,
+    )
+    const [holderState] = PublicKey.findProgramAddressSync(
+      [Buffer.from('user'), nftOracle.toBuffer(), holder.publicKey.toBuffer()],
+      program.programId,
+    )
+    const [nftEscrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from('nft_escrow'), nftOracle.toBuffer(), holder.publicKey.toBuffer(), nftMint.toBuffer()],
+      program.programId,
+    )
+    const [nftLock] = PublicKey.findProgramAddressSync(
+      [Buffer.from('nft_lock'), nftEscrow.toBuffer()],
+      program.programId,
+    )
+
+    await program.methods
+      .initializeNft(defaultParams({
+        name: 'Locked Collection Oracle',
+        description: 'Collection oracle with a deposit lock',
+        depositLockingPeriod: new anchor.BN(3_600),
+      }))
+      .accounts({
+        payer: wallet.publicKey,
+        authority: authority.publicKey,
+        collectionMint,
+        oracleState: nftOracle,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc()
+
+    await program.methods
+      .depositNft()
+      .accounts({
+        user: holder.publicKey,
+        oracleState: nftOracle,
+        nftMint,
+        nftMetadata,
+        userNftAccount: holderNft,
+        nftEscrow,
+        nftLock,
+        userState: holderState,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc()
+
+    await expect(
+      program.methods
+        .withdrawNft()
+        .accounts({
+          user: holder.publicKey,
+          oracleState: nftOracle,
+          nftMint,
+          userNftAccount: holderNft,
+          nftEscrow,
+          nftLock,
+          userState: holderState,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([holder])
+        .rpc(),
+    ).rejects.toThrow(/WithdrawalLocked/)
+    expect(Number((await getAccount(provider.connection, nftEscrow)).amount)).toBe(1)
+  }, 60000)
+
+  it('slashes tokens queued for withdrawal after a disputed report', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Queued Slash Test Oracle',
+      description: 'Oracle whose reporter tries to leave before a dispute',
+      quorum: new anchor.BN(1),
+      withdrawalLockingPeriod: new anchor.BN(3_600),
+      disputeBond: new anchor.BN(1_000_000),
+      disputeVotingPeriod: new anchor.BN(2),
+      disputeSlashBps: new anchor.BN(50_000),
+    })
+    const submitter = await fundedKeypair()
+    const voter = await fundedKeypair()
+    const disputer = await fundedKeypair()
+    await feed.deposit(submitter, 4_000)
+    await feed.deposit(voter, 20_000)
+    await feed.submit(submitter, 100)
+
+    // The reporter queues everything before anyone challenges the report.
+    await program.methods
+      .requestWithdrawal(new anchor.BN(4_000))
+      .accounts({
+        user: submitter.publicKey,
+        oracleState: feed.oracle,
+        userState: feed.userStateOf(submitter.publicKey),
+      } as never)
+      .signers([submitter])
+      .rpc()
+
+    const submitted = (await program.account.userState.fetch(feed.userStateOf(submitter.publicKey)))
+      .recentSubmissions[0]
+    const [dispute] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('dispute'),
+        feed.oracle.toBuffer(),
+        submitter.publicKey.toBuffer(),
+        submitted.sequence.toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    )
+    await nextSlot()
+    await program.methods
+      .openDispute(submitted.sequence, new anchor.BN(90))
+      .accounts({
+        disputer: disputer.publicKey,
+        oracleState: feed.oracle,
+        submitterState: feed.userStateOf(submitter.publicKey),
+        dispute,
+      } as never)
+      .signers([disputer])
+      .rpc()
+    await program.methods
+      .voteDispute(true)
+      .accounts({
+        voter: voter.publicKey,
+        oracleState: feed.oracle,
+        userState: feed.userStateOf(voter.publicKey),
+        dispute,
+      } as never)
+      .signers([voter])
+      .rpc()
+
+    await expect(
+      program.methods
+        .completeWithdrawal()
+        .accounts((await feed.stakeAccounts(submitter)) as never)
+        .signers([submitter])
+        .rpc(),
+    ).rejects.toThrow(/StakeUnderDispute/)
+
+    await sleep(3000)
+    const disputerTokenAccount = await feed.tokenAccountOf(disputer.publicKey)
+    await program.methods
+      .resolveDispute()
+      .accounts({
+        oracleState: feed.oracle,
+        dispute,
+        disputer: disputer.publicKey,
+        submitterState: feed.userStateOf(submitter.publicKey),
+        weightMint: feed.mint,
+        oracleVault: feed.vault,
+        disputerTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        nativeVault: null,
+        submitterDelegation: null,
+        delegateState: null,
+      } as never)
+      .rpc()
+
+    expect(Number((await getAccount(provider.connection, disputerTokenAccount)).amount)).toBe(2_000)
+    const slashed = await program.account.userState.fetch(feed.userStateOf(submitter.publicKey))
+    expect(slashed.pendingWithdrawals.length).toBe(1)
+    expect(slashed.pendingWithdrawals[0].amount.toNumber()).toBe(2_000)
+    expect(slashed.openDisputes.toNumber()).toBe(0)
+  }, 60000)
+
+  it('measures proposal votes, quorum and the vote minimum in vote weight', async () => {
+    const feed = await setupTokenOracle({
+      name: 'Vote Weight Oracle',
+      description: 'Oracle governed by square-root vote weight',
+      weightingMode: { squareRoot: {} },
+      quorum: new anchor.BN(100),
+      minVoteStake: new anchor.BN(100),
+      proposalVotingPeriod: new anchor.BN(2),
+      proposalExecutionGrace: new anchor.BN(3_600),
+    })
+    const staker = wallet.payer
+    // 10_000 tokens carry a vote weight of 100.
+    await feed.deposit(staker, 10_000)
+    expect((await feed.fetchState()).totalVoteWeight.toNumber()).toBe(100)
+    await nextSlot()
+
+    const proposalId = new anchor.BN(0)
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), feed.oracle.toBuffer(), proposalId.toArrayLike(Buffer, 'le', 8)],
+      program.programId,
+    )
+    const [proposalVote] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal_vote'), proposal.toBuffer(), staker.publicKey.toBuffer()],
+      program.programId,
+    )
+    await program.methods
+      .createProposal(proposalId, { rewardBps: new anchor.BN(1_000), resetCircuitBreaker: false } as never)
+      .accounts({
+        proposer: staker.publicKey,
+        oracleState: feed.oracle,
+        userState: feed.userStateOf(staker.publicKey),
+        proposal,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+    await program.methods
+      .voteProposal(true)
+      .accounts({
+        voter: staker.publicKey,
+        oracleState: feed.oracle,
+        userState: feed.userStateOf(staker.publicKey),
+        proposal,
+        proposalVote,
+        systemProgram: SystemProgram.programId,
+      } as never)
+      .rpc()
+    expect((await program.account.proposal.fetch(proposal)).yesVotes.toNumber()).toBe(100)
+
+    // 100 votes do not exceed a quorum of 100.
+    await sleep(3000)
+    await expect(
+      program.methods.executeProposal().accounts({ oracleState: feed.oracle, proposal } as never).rpc(),
+    ).rejects.toThrow(/ProposalRejected/)
+  }, 60000)
 })
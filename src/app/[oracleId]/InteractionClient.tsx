@@ -130,7 +130,7 @@ const extractErrorMessage = (error: unknown, fallback: string) => {
 type UserStateSnapshot = {
   locked: number
   unlocked: number
-  depositTimestamp: number
+  nextUnlockTime: number
   lastOperationTimestamp: number
 }
 
@@ -164,6 +164,23 @@ export default function InteractionClient() {
   const [userTokenBalance, setUserTokenBalance] = useState<number | null>(null)
   const [userStateInfo, setUserStateInfo] = useState<UserStateSnapshot | null>(null)
   const [showVoteHistory, setShowVoteHistory] = useState(false)
+  const [tokenProgramId, setTokenProgramId] = useState<PublicKey>(TOKEN_PROGRAM_ID)
+
+  useEffect(() => {
+    if (!oracle) {
+      return
+    }
+    let cancelled = false
+    // The weight mint may belong to either SPL Token or Token-2022.
+    void connection.getAccountInfo(new PublicKey(oracle.weightMint)).then((mintAccount) => {
+      if (!cancelled && mintAccount) {
+        setTokenProgramId(mintAccount.owner)
+      }
+    })
+    return () => {
+      cancelled = true
+    }
+  }, [oracle, connection])
 
   useEffect(() => {
     setSubmitValue("")
@@ -200,12 +217,17 @@ export default function InteractionClient() {
         throw new Error("Oracle data unavailable")
       }
       const weightMintPk = new PublicKey(oracle.weightMint)
-      const userTokenAccount = getAssociatedTokenAddressSync(weightMintPk, userPk, false)
-      const oracleVaultPk = getAssociatedTokenAddressSync(weightMintPk, oraclePk, true)
+      const userTokenAccount = getAssociatedTokenAddressSync(weightMintPk, userPk, false, tokenProgramId)
+      const oracleVaultPk = getAssociatedTokenAddressSync(weightMintPk, oraclePk, true, tokenProgramId)
       const [userStatePk] = deriveUserStatePda(oraclePk, userPk)
-      return { weightMintPk, userTokenAccount, oracleVaultPk, userStatePk }
+      // Receipt accounts are optional; null tells Anchor they are omitted.
+      const receiptMintPk = oracle.receiptMint ? new PublicKey(oracle.receiptMint) : null
+      const userReceiptAccount = receiptMintPk
+        ? getAssociatedTokenAddressSync(receiptMintPk, userPk, false, tokenProgramId)
+        : null
+      return { weightMintPk, userTokenAccount, oracleVaultPk, userStatePk, receiptMintPk, userReceiptAccount }
     },
-    [oracle, deriveUserStatePda],
+    [oracle, deriveUserStatePda, tokenProgramId],
   )
 
   useEffect(() => {
@@ -246,10 +268,8 @@ export default function InteractionClient() {
             const unlockedRaw = account.unlockedTokens as unknown as BN | number | undefined
             const locked = typeof lockedRaw === "number" ? lockedRaw : lockedRaw?.toNumber?.() ?? 0
             const unlocked = typeof unlockedRaw === "number" ? unlockedRaw : unlockedRaw?.toNumber?.() ?? 0
-            const depositTimestamp =
-              typeof account.depositTimestamp === "number"
-                ? account.depositTimestamp
-                : account.depositTimestamp?.toNumber?.() ?? 0
+            const unlockTimes = (account.depositLots ?? []).map((lot) => lot.unlockTime.toNumber())
+            const nextUnlockTime = unlockTimes.length > 0 ? Math.min(...unlockTimes) : 0
             const lastOperationTimestamp =
               typeof account.lastOperationTimestamp === "number"
                 ? account.lastOperationTimestamp
@@ -258,7 +278,7 @@ export default function InteractionClient() {
             setUserStateInfo({
               locked,
               unlocked,
-              depositTimestamp,
+              nextUnlockTime,
               lastOperationTimestamp,
             })
           }
@@ -298,7 +318,7 @@ export default function InteractionClient() {
         ataPk,
         ownerPk,
         mintPk,
-        TOKEN_PROGRAM_ID,
+        tokenProgramId,
         ASSOCIATED_TOKEN_PROGRAM_ID,
       )
 
@@ -329,7 +349,7 @@ export default function InteractionClient() {
 
       throw new Error("Wallet does not support sending transactions")
     },
-    [connection, wallet],
+    [connection, wallet, tokenProgramId],
   )
 
   const depositTokens = useCallback(async () => {
@@ -357,10 +377,14 @@ export default function InteractionClient() {
     try {
       setIsDepositing(true)
       const oraclePk = new PublicKey(oracle.address)
-      const { weightMintPk, userTokenAccount, oracleVaultPk, userStatePk } = deriveOracleAccounts(oraclePk, wallet.publicKey)
+      const { weightMintPk, userTokenAccount, oracleVaultPk, userStatePk, receiptMintPk, userReceiptAccount } =
+        deriveOracleAccounts(oraclePk, wallet.publicKey)
 
       await ensureAssociatedTokenAccount(weightMintPk, wallet.publicKey, userTokenAccount)
       await ensureAssociatedTokenAccount(weightMintPk, oraclePk, oracleVaultPk)
+      if (receiptMintPk && userReceiptAccount) {
+        await ensureAssociatedTokenAccount(receiptMintPk, wallet.publicKey, userReceiptAccount)
+      }
 
       await program.methods
         .depositTokens(amountBn)
@@ -371,7 +395,9 @@ export default function InteractionClient() {
           userTokenAccount,
           oracleVault: oracleVaultPk,
           userState: userStatePk,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          receiptMint: receiptMintPk,
+          userReceiptAccount,
+          tokenProgram: tokenProgramId,
           systemProgram: SystemProgram.programId,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
@@ -391,7 +417,7 @@ export default function InteractionClient() {
     } finally {
       setIsDepositing(false)
     }
-  }, [oracle, wallet, depositAmount, deriveOracleAccounts, ensureAssociatedTokenAccount, program, toast, refetch])
+  }, [oracle, wallet, depositAmount, deriveOracleAccounts, ensureAssociatedTokenAccount, program, toast, refetch, tokenProgramId])
 
   const withdrawTokens = useCallback(async () => {
     if (!oracle) {
@@ -418,10 +444,34 @@ export default function InteractionClient() {
     try {
       setIsWithdrawing(true)
       const oraclePk = new PublicKey(oracle.address)
-      const { weightMintPk, userTokenAccount, oracleVaultPk, userStatePk } = deriveOracleAccounts(oraclePk, wallet.publicKey)
+      const { weightMintPk, userTokenAccount, oracleVaultPk, userStatePk, receiptMintPk, userReceiptAccount } =
+        deriveOracleAccounts(oraclePk, wallet.publicKey)
 
       await ensureAssociatedTokenAccount(weightMintPk, wallet.publicKey, userTokenAccount)
 
+      if (oracle.withdrawalLockingPeriod > 0) {
+        // Stake leaves through the unbonding queue; claimWithdrawals pays it out.
+        await program.methods
+          .requestWithdrawal(amountBn)
+          .accounts({
+            user: wallet.publicKey,
+            oracleState: oraclePk,
+            userState: userStatePk,
+            receiptMint: receiptMintPk,
+            userReceiptAccount,
+            tokenProgram: tokenProgramId,
+          } as never)
+          .rpc()
+
+        toast({
+          title: "Withdrawal requested",
+          description: `Tokens can be claimed after ${formatDuration(oracle.withdrawalLockingPeriod)}.`,
+        })
+        setWithdrawAmount("")
+        void refetch()
+        return
+      }
+
       await program.methods
         .withdrawTokens(amountBn)
         .accounts({
@@ -431,7 +481,9 @@ export default function InteractionClient() {
           userTokenAccount,
           oracleVault: oracleVaultPk,
           userState: userStatePk,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          receiptMint: receiptMintPk,
+          userReceiptAccount,
+          tokenProgram: tokenProgramId,
         } as never)
         .rpc()
 
@@ -448,7 +500,47 @@ export default function InteractionClient() {
     } finally {
       setIsWithdrawing(false)
     }
-  }, [oracle, wallet, withdrawAmount, deriveOracleAccounts, ensureAssociatedTokenAccount, program, toast, refetch])
+  }, [oracle, wallet, withdrawAmount, deriveOracleAccounts, ensureAssociatedTokenAccount, program, toast, refetch, tokenProgramId])
+
+  const claimWithdrawals = useCallback(async () => {
+    if (!oracle || !wallet.connected || !wallet.publicKey) {
+      toast({ title: "Wallet required", description: "Connect your wallet to claim withdrawals.", variant: "destructive" })
+      return
+    }
+
+    try {
+      setIsWithdrawing(true)
+      const oraclePk = new PublicKey(oracle.address)
+      const { weightMintPk, userTokenAccount, oracleVaultPk, userStatePk } = deriveOracleAccounts(oraclePk, wallet.publicKey)
+
+      await ensureAssociatedTokenAccount(weightMintPk, wallet.publicKey, userTokenAccount)
+
+      await program.methods
+        .completeWithdrawal()
+        .accounts({
+          user: wallet.publicKey,
+          weightMint: weightMintPk,
+          oracleState: oraclePk,
+          userTokenAccount,
+          oracleVault: oracleVaultPk,
+          userState: userStatePk,
+          tokenProgram: tokenProgramId,
+        } as never)
+        .rpc()
+
+      toast({ title: "Withdrawal complete", description: "Unbonded tokens returned to your wallet." })
+      void refetch()
+    } catch (claimError: unknown) {
+      console.error("Failed to claim withdrawals", claimError)
+      toast({
+        title: "Claim failed",
+        description: extractErrorMessage(claimError, "No withdrawals have finished unbonding."),
+        variant: "destructive",
+      })
+    } finally {
+      setIsWithdrawing(false)
+    }
+  }, [oracle, wallet, deriveOracleAccounts, ensureAssociatedTokenAccount, program, toast, refetch, tokenProgramId])
 
   const submitPrice = useCallback(async () => {
     if (!oracle) {
@@ -476,6 +568,12 @@ export default function InteractionClient() {
       setIsSubmittingPrice(true)
       const oraclePk = new PublicKey(oracle.address)
       const [userState] = deriveUserStatePda(oraclePk, wallet.publicKey)
+      // Permissioned oracles require the staker's permit; open ones ignore it.
+      const [permitPk] = PublicKey.findProgramAddressSync(
+        [Buffer.from("submitter"), oraclePk.toBuffer(), wallet.publicKey.toBuffer()],
+        ORACLE_PROGRAM_ID,
+      )
+      const submitterPermit = (await connection.getAccountInfo(permitPk)) ? permitPk : null
 
       await program.methods
         .submitValue(priceBn)
@@ -483,6 +581,8 @@ export default function InteractionClient() {
           user: wallet.publicKey,
           oracleState: oraclePk,
           userState,
+          owner: wallet.publicKey,
+          submitterPermit,
         } as never)
         .rpc()
 
@@ -499,7 +599,7 @@ export default function InteractionClient() {
     } finally {
       setIsSubmittingPrice(false)
     }
-  }, [oracle, wallet, submitValue, program, deriveUserStatePda, refetch, toast])
+  }, [oracle, wallet, connection, submitValue, program, deriveUserStatePda, refetch, toast])
 
   const vote = useCallback(
     async (kind: "blacklist" | "whitelist") => {
@@ -914,10 +1014,20 @@ export default function InteractionClient() {
           ) : (
             <>
               <Download className="mr-2 h-4 w-4" />
-              Withdraw Tokens
+              {oracle.withdrawalLockingPeriod > 0 ? "Request Withdrawal" : "Withdraw Tokens"}
             </>
           )}
         </Button>
+        {oracle.withdrawalLockingPeriod > 0 ? (
+          <Button
+            variant="outline"
+            onClick={claimWithdrawals}
+            disabled={!walletConnected || isWithdrawing}
+            className="w-full h-12 rounded-xl"
+          >
+            Claim Unbonded Tokens
+          </Button>
+        ) : null}
       </CardContent>
     </Card>
   )
@@ -1042,9 +1152,9 @@ export default function InteractionClient() {
                 <div className="text-sm font-medium text-foreground">{formatTokenAmount(totalUnlockedTokens)} tokens</div>
               </div>
             </div>
-            {userHasDeposits && userStateInfo?.depositTimestamp ? (
+            {userHasDeposits && userStateInfo?.nextUnlockTime ? (
               <p className="text-xs text-muted-foreground">
-                Deposited since {formatHistoryTimestamp(userStateInfo.depositTimestamp)}
+                Next deposit unlocks {formatHistoryTimestamp(userStateInfo.nextUnlockTime)}
               </p>
             ) : null}
           </div>
@@ -11,7 +11,12 @@ import { Textarea } from '@/components/ui/textarea'
 import { useToast } from '@/components/ui/use-toast'
 import { useConnection, useWallet } from '@solana/wallet-adapter-react'
 import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js'
-import { getAssociatedTokenAddressSync, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import {
+  getAssociatedTokenAddressSync,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+} from '@solana/spl-token'
 import { BN } from '@coral-xyz/anchor'
 import { getOracleProgram, ORACLE_PROGRAM_ID } from '@/lib/oracleProgram'
 import { DEFAULT_CLUSTER } from '@/utils/cluster'
@@ -247,17 +252,16 @@ export default function CreateOracleIntegrated() {
         setErrors((prev) => ({ ...prev, weightToken: 'Weight token mint account not found on this cluster' }))
         throw new Error('Weight token mint account not found on this cluster')
       }
-      if (!mintAccount.owner.equals(TOKEN_PROGRAM_ID)) {
-        throw new Error(
-          'Weight token mint must be owned by the SPL Token program (Tokenkeg...). Please create the mint with the classic SPL Token program.'
-        )
+      const tokenProgram = mintAccount.owner
+      if (!tokenProgram.equals(TOKEN_PROGRAM_ID) && !tokenProgram.equals(TOKEN_2022_PROGRAM_ID)) {
+        throw new Error('Weight token mint must be owned by the SPL Token or Token-2022 program.')
       }
 
       const [oracleState] = PublicKey.findProgramAddressSync(
         [Buffer.from('oracle'), wallet.publicKey.toBuffer(), weightMintKey.toBuffer()],
         ORACLE_PROGRAM_ID
       )
-      const oracleVault = getAssociatedTokenAddressSync(weightMintKey, oracleState, true)
+      const oracleVault = getAssociatedTokenAddressSync(weightMintKey, oracleState, true, tokenProgram)
 
       const existingState = await connection.getAccountInfo(oracleState)
       if (existingState) {
@@ -277,10 +281,37 @@ export default function CreateOracleIntegrated() {
         description: description.trim(),
         rewardBps: toBN(rewardBps),
         halfLifeSeconds: toBN(halfLifeSeconds),
-        quorum: toBN(quorumBps),
+        quorum: new BN(0),
+        // The form uses 1/10_000 basis points; the program's denominator is 100_000.
+        quorumBps: toBN(quorumBps).muln(10),
         depositLockingPeriod: toBN(depositLock),
         withdrawalLockingPeriod: toBN(withdrawLock),
         alpha: toBN(alpha),
+        decayModel: { exponential: {} },
+        earlyExitPenaltyBps: new BN(0),
+        treasury: getAssociatedTokenAddressSync(weightMintKey, wallet.publicKey, false, tokenProgram),
+        heartbeatSeconds: new BN(0),
+        maxUpdateDeviationBps: new BN(0),
+        maxWindowDeviationBps: new BN(0),
+        deviationWindowSeconds: new BN(0),
+        veMaxLockSeconds: new BN(0),
+        veMaxBoostBps: new BN(0),
+        maxUserWeight: new BN(0),
+        maxUserWeightBps: new BN(0),
+        weightingMode: { linear: {} },
+        minSubmitStake: new BN(0),
+        minVoteStake: new BN(0),
+        minSubmissionInterval: new BN(0),
+        accessMode: { open: {} },
+        proposalVotingPeriod: new BN(0),
+        proposalExecutionDelay: new BN(0),
+        proposalExecutionGrace: new BN(0),
+        appealBond: new BN(0),
+        appealVotingPeriod: new BN(0),
+        minBlacklistDuration: new BN(0),
+        disputeBond: new BN(0),
+        disputeVotingPeriod: new BN(0),
+        disputeSlashBps: new BN(0),
       }
 
       const signatureResult = await program.methods
@@ -292,7 +323,7 @@ export default function CreateOracleIntegrated() {
           oracleState,
           oracleVault,
           systemProgram: SystemProgram.programId,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenProgram,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           rent: SYSVAR_RENT_PUBKEY,
         } as never)
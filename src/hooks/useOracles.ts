@@ -133,6 +133,7 @@ export interface OracleSummary {
 export interface OracleDetail extends OracleSummary {
   authority: string
   weightMint: string
+  receiptMint: string | null
   totalDepositedTokens: string
   aggregatedWeight: string
   rewardBps: number
@@ -141,6 +142,7 @@ export interface OracleDetail extends OracleSummary {
   depositLockingPeriod: number
   withdrawalLockingPeriod: number
   alpha: number
+  heartbeatSeconds: number
   priceHistory: PriceHistoryPoint[]
   targets: GovernanceTarget[]
 }
@@ -168,6 +170,7 @@ type OracleAccountRaw = {
   description?: string | null
   authority?: PublicKey | string | null
   weightMint?: PublicKey | string | null
+  receiptMint?: PublicKey | string | null
   totalDepositedTokens?: BN | number | string | null
   aggregatedWeight?: BN | number | string | null
   rewardBps?: BN | number | null
@@ -176,7 +179,8 @@ type OracleAccountRaw = {
   depositLockingPeriod?: BN | number | null
   withdrawalLockingPeriod?: BN | number | null
   alpha?: BN | number | null
-  priceHistory?: PriceHistoryRecord[] | null
+  heartbeatSeconds?: BN | number | null
+  status?: Record<string, unknown> | null
   targets?: GovernanceTargetRaw[] | null
   lastSubmissionTime?: BN | number | null
   lastTimestamp?: BN | number | null
@@ -184,7 +188,10 @@ type OracleAccountRaw = {
   latestValue?: BN | number | null
 }
 
-const toOracleDetail = (accountWithPk: AccountWithPublicKey<OracleAccountRaw>): OracleDetail => {
+const toOracleDetail = (
+  accountWithPk: AccountWithPublicKey<OracleAccountRaw>,
+  priceHistoryRecords: PriceHistoryRecord[] = [],
+): OracleDetail => {
   const { publicKey, account } = accountWithPk
 
   const resolvePubkey = (value: unknown): string => {
@@ -206,7 +213,6 @@ const toOracleDetail = (accountWithPk: AccountWithPublicKey<OracleAccountRaw>):
     }
   }
 
-  const priceHistoryRecords = Array.isArray(account.priceHistory) ? account.priceHistory : []
   const priceHistory: PriceHistoryPoint[] = priceHistoryRecords.map((record) => ({
     timestamp: record?.timestamp instanceof BN ? record.timestamp.toNumber() : Number(record?.timestamp ?? 0),
     aggregated: bnToFloat(record?.aggregatedValue ?? null),
@@ -221,14 +227,22 @@ const toOracleDetail = (accountWithPk: AccountWithPublicKey<OracleAccountRaw>):
     isBlacklisted: Boolean(target?.isBlacklisted),
   }))
 
+  const receiptMint = resolvePubkey(account.receiptMint)
   const halfLifeSeconds = account.halfLifeSeconds instanceof BN ? account.halfLifeSeconds.toNumber() : Number(account.halfLifeSeconds ?? 0)
+  const status: OracleSummary['status'] = !account.status
+    ? 'active'
+    : 'halted' in account.status
+      ? 'maintenance'
+      : 'stale' in account.status
+        ? 'inactive'
+        : 'active'
 
   return {
     id: publicKey.toBase58(),
     address: publicKey.toBase58(),
     name: account.name ?? 'Unnamed Oracle',
     description: account.description ?? 'No description provided',
-    status: 'active',
+    status,
     category: 'Price Feed',
     updateFrequency: halfLifeSeconds > 0 ? `${halfLifeSeconds}s` : 'manual',
     accuracy: 'On-chain verified',
@@ -239,6 +253,7 @@ const toOracleDetail = (accountWithPk: AccountWithPublicKey<OracleAccountRaw>):
     latestValue: bnToDecimalString(account.latestValue ?? null),
     authority: resolvePubkey(account.authority),
     weightMint: resolvePubkey(account.weightMint),
+    receiptMint: receiptMint === 'Unknown' || receiptMint === PublicKey.default.toBase58() ? null : receiptMint,
     totalDepositedTokens: account.totalDepositedTokens?.toString?.() ?? '0',
     aggregatedWeight: account.aggregatedWeight?.toString?.() ?? '0',
     rewardBps: account.rewardBps instanceof BN ? account.rewardBps.toNumber() : Number(account.rewardBps ?? 0),
@@ -249,6 +264,8 @@ const toOracleDetail = (accountWithPk: AccountWithPublicKey<OracleAccountRaw>):
     withdrawalLockingPeriod:
       account.withdrawalLockingPeriod instanceof BN ? account.withdrawalLockingPeriod.toNumber() : Number(account.withdrawalLockingPeriod ?? 0),
     alpha: account.alpha instanceof BN ? account.alpha.toNumber() : Number(account.alpha ?? 0),
+    heartbeatSeconds:
+      account.heartbeatSeconds instanceof BN ? account.heartbeatSeconds.toNumber() : Number(account.heartbeatSeconds ?? 0),
     priceHistory,
     targets,
   }
@@ -284,7 +301,7 @@ export function useOracles() {
     setError(null)
     try {
       const accounts = await program.account.oracleState.all()
-      const details = accounts.map(toOracleDetail).map(toOracleSummary)
+      const details = accounts.map((account) => toOracleSummary(toOracleDetail(account)))
       setOracles(details)
     } catch (err) {
       console.error('Failed to fetch oracles', err)
@@ -327,8 +344,15 @@ export function useOracle(address?: string) {
       setLoading(true)
       setError(null)
       const publicKey = new PublicKey(address)
-      const account = await program.account.oracleState.fetch(publicKey)
-      setOracle(toOracleDetail({ publicKey, account }))
+      const [historyAddress] = PublicKey.findProgramAddressSync(
+        [Buffer.from('history'), publicKey.toBuffer()],
+        program.programId,
+      )
+      const [account, history] = await Promise.all([
+        program.account.oracleState.fetch(publicKey),
+        program.account.priceHistory.fetchNullable(historyAddress),
+      ])
+      setOracle(toOracleDetail({ publicKey, account }, history?.records ?? []))
     } catch (err) {
       console.error('Failed to fetch oracle', err)
       setError('Unable to fetch oracle account data')
@@ -8,8 +8,9 @@ import type { Oracle } from '@project/anchor/types/oracle'
 import { DEFAULT_COMMITMENT } from '@/utils/cluster'
 
 const ACCOUNT_SIZES: Record<string, number> = {
-  OracleState: 8841,
-  UserState: 5290,
+  OracleState: 5332,
+  PriceHistory: 7469,
+  UserState: 6810,
 }
 
 type IdlTypeEntry = {